@@ -0,0 +1,219 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Answer to one part of a puzzle, as returned by every solver. Most puzzles ask for a number, some
+/// for text such as a comma separated list.
+///
+/// Answers compare by what they display, so `Signed(5)`, `Unsigned(5)`, `Big(5)` and `Text("5")`
+/// are all equal. They are serialized as that text too, which keeps numbers beyond `i64` safe in
+/// TOML.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(i128),
+    Text(String),
+}
+
+impl Answer {
+    fn integer(&self) -> Option<i128> {
+        match *self {
+            Self::Signed(n) => Some(n.into()),
+            Self::Unsigned(n) => Some(n.into()),
+            Self::Big(n) => Some(n),
+            Self::Text(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(n) => n.fmt(f),
+            Self::Unsigned(n) => n.fmt(f),
+            Self::Big(n) => n.fmt(f),
+            Self::Text(text) => text.fmt(f),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.integer(), other.integer()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string() == other.to_string(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+/// Lets answers be checked against plain numbers, as in `assert_eq!(answer, 11)`.
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        *self == Self::Unsigned(*other)
+    }
+}
+
+/// Reads the smallest integer variant that holds the text, falling back to [`Answer::Text`]. Only
+/// text that an integer displays as exactly is read as one, so `"042"` or `"+5"` stay text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        let answer = if let Ok(n) = s.parse() {
+            Self::Unsigned(n)
+        } else if let Ok(n) = s.parse() {
+            Self::Signed(n)
+        } else if let Ok(n) = s.parse() {
+            Self::Big(n)
+        } else {
+            return Ok(Self::Text(s.to_string()));
+        };
+        Ok(if answer.to_string() == s {
+            answer
+        } else {
+            Self::Text(s.to_string())
+        })
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Self::Signed(n)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Self::Unsigned(n)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Unsigned(n as u64)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        Self::Big(n)
+    }
+}
+
+/// Numbers beyond `i128` are kept as their digits.
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Self::Text(n.to_string()), Self::Big)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+struct AnswerVisitor;
+
+impl Visitor<'_> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer or a string")
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+        Ok(Answer::Signed(n))
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+        Ok(Answer::Unsigned(n))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+        Ok(s.parse().unwrap_or_else(|e: Infallible| match e {}))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_compare_by_value() {
+        assert_eq!(Answer::Signed(42), Answer::Unsigned(42));
+        assert_eq!(Answer::Big(42), Answer::from(42usize));
+        assert_eq!(Answer::from("42"), Answer::Unsigned(42));
+        assert_eq!(Answer::from("6,0,4"), Answer::Text(String::from("6,0,4")));
+        assert_ne!(Answer::Signed(-1), Answer::Unsigned(u64::MAX));
+        assert_ne!(Answer::from("042"), Answer::Unsigned(42));
+        assert_eq!(Answer::Signed(42), 42);
+        assert_ne!(Answer::Signed(-1), u64::MAX);
+    }
+
+    #[test]
+    fn parses_the_smallest_variant() {
+        let parse = |s: &str| s.parse::<Answer>().unwrap();
+        assert!(matches!(
+            parse("18446744073709551615"),
+            Answer::Unsigned(u64::MAX)
+        ));
+        assert!(matches!(parse("-7"), Answer::Signed(-7)));
+        assert!(matches!(parse("18446744073709551616"), Answer::Big(_)));
+        assert!(matches!(parse("4,6,3,5"), Answer::Text(_)));
+        assert!(matches!(parse("007"), Answer::Text(_)));
+    }
+
+    #[test]
+    fn serializes_as_text() {
+        let answers = [
+            Answer::Signed(-3),
+            Answer::Unsigned(u64::MAX),
+            Answer::Big(i128::MAX),
+            Answer::from("LRRL"),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            format!(r#"["-3","{}","{}","LRRL"]"#, u64::MAX, i128::MAX)
+        );
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json).unwrap(), answers);
+        for text in ["042", "+5", "-0"] {
+            let json = serde_json::to_string(&Answer::from(text)).unwrap();
+            let answer = serde_json::from_str::<Answer>(&json).unwrap();
+            assert!(
+                matches!(&answer, Answer::Text(t) if t == text),
+                "{:?}",
+                answer
+            );
+        }
+        assert_eq!(
+            serde_json::from_str::<Answer>("12").unwrap(),
+            Answer::from(12u64)
+        );
+    }
+}
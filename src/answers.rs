@@ -0,0 +1,68 @@
+use std::{fs, io::ErrorKind, path::Path};
+
+use crate::answer::Answer;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+pub const ANSWERS: &str = "answers.toml";
+
+/// Known correct answers, keyed by day, part and a hash of the input they belong to.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    answers: Vec<StoredAnswer>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StoredAnswer {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub path: String,
+    pub answer: Answer,
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => Ok(toml::from_str(&s)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, part: u8, hash: &str) -> Option<&Answer> {
+        self.answers
+            .iter()
+            .find(|a| a.day == day && a.part == part && a.input == hash)
+            .map(|a| &a.answer)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, hash: String, path: &str, answer: &Answer) {
+        self.answers
+            .retain(|a| !(a.day == day && a.part == part && a.input == hash));
+        self.answers.push(StoredAnswer {
+            day,
+            part,
+            input: hash,
+            path: path.to_string(),
+            answer: answer.clone(),
+        });
+        self.answers.sort_by(|a, b| {
+            (a.day, a.part, &a.path, &a.input).cmp(&(b.day, b.part, &b.path, &b.input))
+        });
+    }
+}
+
+/// 64-bit FNV-1a hash of the input, which unlike `DefaultHasher` is stable between releases.
+pub fn hash_input(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,115 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answer::Answer,
+    memory::MemoryProbe,
+    solution::{Phase, Probe, Solver},
+};
+use anyhow::Result;
+
+/// Summary of the timings collected for one phase over every measured iteration.
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+pub struct BenchResult {
+    pub answer: Answer,
+    pub parse: Stats,
+    pub solve: Stats,
+    pub memory: MemoryProbe,
+}
+
+/// [`Probe`] that records how long every phase took.
+#[derive(Default)]
+pub struct Timer {
+    started: Option<Instant>,
+    pub parse: Vec<Duration>,
+    pub solve: Vec<Duration>,
+}
+
+impl Probe for Timer {
+    fn start(&mut self, _phase: Phase) {
+        self.started = Some(Instant::now());
+    }
+
+    fn stop(&mut self, phase: Phase) {
+        let elapsed = self.started.take().map_or(Duration::ZERO, |s| s.elapsed());
+        match phase {
+            Phase::Parse => self.parse.push(elapsed),
+            Phase::Solve => self.solve.push(elapsed),
+        }
+    }
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len().max(1);
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / (n.max(2) - 1) as f64;
+        Self {
+            min: samples.first().copied().unwrap_or_default(),
+            median: samples.get(samples.len() / 2).copied().unwrap_or_default(),
+            mean,
+            p95: samples
+                .get((samples.len() * 95).div_ceil(100).saturating_sub(1))
+                .copied()
+                .unwrap_or_default(),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let us = |d: Duration| d.as_secs_f64() * 1_000_000.0;
+        write!(
+            f,
+            "min {:>10.1} us  median {:>10.1} us  mean {:>10.1} us  p95 {:>10.1} us  stddev {:>10.1} us",
+            us(self.min),
+            us(self.median),
+            us(self.mean),
+            us(self.p95),
+            us(self.stddev)
+        )
+    }
+}
+
+/// Runs `part` of `solver` on `input` `warmup` times unmeasured and then `iterations` times,
+/// timing the parse and solve phases of every measured run. Memory is sampled on one more run
+/// afterwards so the allocator bookkeeping does not show up in the timings.
+pub fn bench(
+    solver: &dyn Solver,
+    part: u8,
+    input: &str,
+    warmup: usize,
+    iterations: usize,
+) -> Result<BenchResult> {
+    for _ in 0..warmup {
+        solver.solve(part, input.to_string())?;
+    }
+    let mut timer = Timer::default();
+    for _ in 0..iterations {
+        solver.solve_with(part, input.to_string(), &mut timer)?;
+    }
+    let mut memory = MemoryProbe::default();
+    let answer = solver.solve_with(part, input.to_string(), &mut memory)?;
+    Ok(BenchResult {
+        answer,
+        parse: Stats::new(timer.parse),
+        solve: Stats::new(timer.solve),
+        memory,
+    })
+}
@@ -0,0 +1,278 @@
+use crate::{
+    grid::Grid,
+    point::{Cardinal, Point},
+    search::Visited,
+};
+
+/// Fixed capacity set of the integers below its capacity, one bit each.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl BitSet {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(64)],
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn bit(&self, i: usize) -> (usize, u64) {
+        assert!(
+            i < self.capacity,
+            "{} is beyond the bit set's capacity of {}",
+            i,
+            self.capacity
+        );
+        (i / 64, 1 << (i % 64))
+    }
+
+    /// Adds `i`, returning whether it was not already in the set.
+    pub fn insert(&mut self, i: usize) -> bool {
+        let (word, mask) = self.bit(i);
+        let absent = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        absent
+    }
+
+    /// Removes `i`, returning whether it was in the set.
+    pub fn remove(&mut self, i: usize) -> bool {
+        let (word, mask) = self.bit(i);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.capacity && self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    i * 64 + bit
+                })
+            })
+        })
+    }
+}
+
+/// Row-major index of `position` in a `width` by `height` grid, if it lies inside it.
+fn cell_index(width: usize, height: usize, position: Point) -> Option<usize> {
+    let inside =
+        (0..width as i64).contains(&position.x) && (0..height as i64).contains(&position.y);
+    inside.then(|| position.y as usize * width + position.x as usize)
+}
+
+/// Set of the positions of a grid, one bit per cell instead of a hashed entry. Inserting or
+/// removing a position outside the grid panics, like indexing the grid would.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PointSet {
+    bits: BitSet,
+    width: usize,
+    height: usize,
+}
+
+impl PointSet {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: BitSet::new(width * height),
+            width,
+            height,
+        }
+    }
+
+    /// An empty set covering every cell of `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    fn index(&self, position: Point) -> usize {
+        cell_index(self.width, self.height, position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+
+    pub fn insert(&mut self, position: Point) -> bool {
+        let i = self.index(position);
+        self.bits.insert(i)
+    }
+
+    pub fn remove(&mut self, position: Point) -> bool {
+        let i = self.index(position);
+        self.bits.remove(i)
+    }
+
+    pub fn contains(&self, position: Point) -> bool {
+        cell_index(self.width, self.height, position).is_some_and(|i| self.bits.contains(i))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.bits
+            .iter()
+            .map(|i| Point::new((i % self.width) as i64, (i / self.width) as i64))
+    }
+}
+
+impl Visited<Point> for PointSet {
+    fn insert(&mut self, position: Point) -> bool {
+        PointSet::insert(self, position)
+    }
+
+    fn contains(&self, position: &Point) -> bool {
+        PointSet::contains(self, *position)
+    }
+}
+
+/// Set of (position, direction) pairs on a grid, such as the states of something walking along
+/// its rows and columns, with one bit for each of the four directions of every cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PointDirectionSet {
+    bits: BitSet,
+    width: usize,
+    height: usize,
+}
+
+impl PointDirectionSet {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: BitSet::new(width * height * Cardinal::ALL.len()),
+            width,
+            height,
+        }
+    }
+
+    /// An empty set covering every cell of `grid`.
+    pub fn for_grid<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.width(), grid.height())
+    }
+
+    fn index(&self, position: Point, direction: Cardinal) -> Option<usize> {
+        cell_index(self.width, self.height, position)
+            .map(|i| i * Cardinal::ALL.len() + direction as usize)
+    }
+
+    pub fn insert(&mut self, position: Point, direction: Cardinal) -> bool {
+        let i = self
+            .index(position, direction)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position));
+        self.bits.insert(i)
+    }
+
+    pub fn contains(&self, position: Point, direction: Cardinal) -> bool {
+        self.index(position, direction)
+            .is_some_and(|i| self.bits.contains(i))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_set_inserts_and_removes() {
+        let mut bits = BitSet::new(130);
+        assert!(bits.is_empty());
+        assert!(bits.insert(0));
+        assert!(bits.insert(129));
+        assert!(!bits.insert(129));
+        assert!(bits.insert(64));
+        assert_eq!(bits.iter().collect::<Vec<_>>(), [0, 64, 129]);
+        assert_eq!(bits.len(), 3);
+        assert!(bits.remove(64));
+        assert!(!bits.remove(64));
+        assert!(!bits.contains(64) && !bits.contains(130));
+        bits.clear();
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "beyond the bit set's capacity")]
+    fn bit_set_rejects_values_past_capacity() {
+        BitSet::new(10).insert(10);
+    }
+
+    #[test]
+    fn point_set_covers_the_grid() {
+        let mut points = PointSet::new(3, 2);
+        assert!(points.insert(Point::new(2, 1)));
+        assert!(points.insert(Point::new(0, 1)));
+        assert!(!points.insert(Point::new(2, 1)));
+        assert!(points.contains(Point::new(0, 1)));
+        assert!(!points.contains(Point::new(3, 0)));
+        assert!(!points.contains(Point::new(-1, 1)));
+        assert_eq!(
+            points.iter().collect::<Vec<_>>(),
+            [Point::new(0, 1), Point::new(2, 1)]
+        );
+        assert!(points.remove(Point::new(0, 1)));
+        assert_eq!(points.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the grid")]
+    fn point_set_rejects_points_outside_the_grid() {
+        PointSet::new(3, 2).insert(Point::new(0, 2));
+    }
+
+    #[test]
+    fn point_direction_set_keeps_directions_apart() {
+        let mut states = PointDirectionSet::new(2, 2);
+        let corner = Point::new(1, 1);
+        assert!(states.insert(corner, Cardinal::North));
+        assert!(states.insert(corner, Cardinal::West));
+        assert!(!states.insert(corner, Cardinal::North));
+        assert!(states.contains(corner, Cardinal::West));
+        assert!(!states.contains(corner, Cardinal::East));
+        assert!(!states.contains(Point::new(1, 0), Cardinal::North));
+        assert_eq!(states.len(), 2);
+        assert_eq!(states.bits.capacity(), 2 * 2 * 4);
+        states.clear();
+        assert!(states.is_empty());
+    }
+}
@@ -1,10 +1,17 @@
 use std::collections::HashMap;
 
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    input::{get_input, Input},
+    parse,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_1.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_1.txt";
 
 struct FrequencyList {
     frequencies: HashMap<i64, i64>,
@@ -38,17 +45,11 @@ impl From<Vec<i64>> for FrequencyList {
 }
 
 impl LocationList {
-    fn new() -> LocationList {
-        LocationList {
-            locations: Vec::new(),
-        }
-    }
-
     fn find_distance(&self, other: &LocationList) -> u64 {
         self.locations
             .iter()
             .zip(other.locations.iter())
-            .fold(0, |acc, (a, b)| acc + (a - b).abs() as u64)
+            .fold(0, |acc, (a, b)| acc + a.abs_diff(*b))
     }
 
     fn find_similarity(&self, frequencies: &FrequencyList) -> u64 {
@@ -73,36 +74,118 @@ impl From<&mut Vec<i64>> for LocationList {
     }
 }
 
-// Elapsed time: 916 us
-// Memory Used: 56.944336 kb
-pub fn list_distance(input_path: &str) -> Result<u64> {
-    let input = get_input(input_path)?;
-    let locs = input
+fn parse_locations(input: &str) -> Result<Vec<(i64, i64)>, ParseError> {
+    input
         .lines()
-        .filter_map(|l| {
-            l.split_once("   ")
-                .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            let [a, b] = parse::tuple(Day1::DAY, input, l, "   ")?;
+            Ok((a, b))
         })
-        .collect::<Vec<(i64, i64)>>();
+        .collect()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Puzzle = Vec<(i64, i64)>;
+
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(parse_locations(&input)?)
+    }
+
+    fn part_1(locs: &Self::Puzzle) -> Result<Answer> {
+        let loc1 = LocationList::from(locs.iter().map(|(a, _)| *a).collect::<Vec<i64>>().as_mut());
+        let loc2 = LocationList::from(locs.iter().map(|(_, b)| *b).collect::<Vec<i64>>().as_mut());
+        Ok(loc1.find_distance(&loc2).into())
+    }
+
+    fn part_2(nums: &Self::Puzzle) -> Result<Answer> {
+        let locs = LocationList::from(nums.iter().map(|(a, _)| *a).collect::<Vec<i64>>());
+        let freqs = FrequencyList::from(nums.iter().map(|(_, b)| *b).collect::<Vec<i64>>());
+        Ok(locs.find_similarity(&freqs).into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let max = 10_000 + 4 * size as u64;
+        (0..size)
+            .map(|_| format!("{}   {}", rng.range(10_000..=max), rng.range(10_000..=max)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
 
-    let loc1 = LocationList::from(locs.iter().map(|(a, _)| *a).collect::<Vec<i64>>().as_mut());
-    let loc2 = LocationList::from(locs.iter().map(|(_, b)| *b).collect::<Vec<i64>>().as_mut());
-    Ok(loc1.find_distance(&loc2))
+// Elapsed time: 916 us
+// Memory Used: 56.944336 kb
+pub fn list_distance<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day1::part_1(&Day1::parse(input)?)
 }
 
 // Elapsed time: 930 us
 // Memory Used: 74.663086 kb
-pub fn get_similarity_score(input_path: &str) -> Result<u64> {
-    let input = get_input(input_path)?;
-    let nums = input
-        .lines()
-        .filter_map(|l| {
-            l.split_once("   ")
-                .map(|(a, b)| (a.parse().unwrap(), b.parse().unwrap()))
-        })
-        .collect::<Vec<(i64, i64)>>();
+pub fn get_similarity_score<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day1::part_2(&Day1::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    #[test]
+    fn list_distance_example() {
+        assert_eq!(list_distance(TEST).unwrap(), 11);
+    }
+
+    #[test]
+    fn get_similarity_score_example() {
+        assert_eq!(get_similarity_score(TEST).unwrap(), 31);
+    }
 
-    let locs = LocationList::from(nums.iter().map(|(a, _)| *a).collect::<Vec<i64>>());
-    let freqs = FrequencyList::from(nums.iter().map(|(_, b)| *b).collect::<Vec<i64>>());
-    Ok(locs.find_similarity(&freqs))
+    #[test]
+    fn solvers_accept_input_text() {
+        let example = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+        assert_eq!(list_distance(Input::Text(example)).unwrap(), 11);
+        assert_eq!(
+            get_similarity_score(Input::reader(example.as_bytes())).unwrap(),
+            31
+        );
+    }
+
+    fn reference_lists(input: &str) -> (Vec<i64>, Vec<i64>) {
+        input
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| {
+                let mut numbers = l.split_whitespace().map(|n| n.parse::<i64>().unwrap());
+                (numbers.next().unwrap(), numbers.next().unwrap())
+            })
+            .unzip()
+    }
+
+    fn reference_part_1(input: &str) -> u64 {
+        let (mut left, mut right) = reference_lists(input);
+        left.sort();
+        right.sort();
+        left.iter().zip(&right).map(|(a, b)| a.abs_diff(*b)).sum()
+    }
+
+    fn reference_part_2(input: &str) -> i64 {
+        let (left, right) = reference_lists(input);
+        left.iter()
+            .map(|a| a * right.iter().filter(|b| *b == a).count() as i64)
+            .sum()
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day1, 20, reference_part_1, reference_part_2);
+    }
 }
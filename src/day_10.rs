@@ -1,107 +1,249 @@
-use std::collections::HashSet;
-
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    grid::Grid,
+    input::{get_input, Input},
+    point::Point,
+    search,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_10.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_10.txt";
 
-const NEIGHBORS: [(i64, i64); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+// Height given to tiles like '.' that no trail can step on
+const IMPASSABLE: u8 = u8::MAX;
 
-struct Mountain {
-    peaks: Vec<Vec<u8>>,
+pub struct Mountain {
+    peaks: Grid<u8>,
 }
 
 impl Mountain {
-    fn new() -> Self {
-        Mountain { peaks: Vec::new() }
-    }
-
     fn find_total_trails(&self) -> u64 {
         self.peaks
-            .iter()
-            .enumerate()
-            .flat_map(|(i, row)| {
-                row.iter().enumerate().map(move |(j, &p)| {
-                    if p == 0 {
-                        self.find_reachable_peaks((i as i64, j as i64))
-                            .iter()
-                            .collect::<HashSet<_>>()
-                            .len() as u64
-                    } else {
-                        0
-                    }
-                })
+            .positions(|&p| p == 0)
+            .map(|start| {
+                search::bfs(start, |&position| self.uphill(position))
+                    .filter(|&(position, _)| self.peaks[position] == 9)
+                    .count() as u64
             })
             .sum()
     }
 
     fn find_total_distinct_trails(&self) -> u64 {
+        // Every step climbs by one, so each trail to a peak is one of its shortest paths
         self.peaks
-            .iter()
-            .enumerate()
-            .flat_map(|(i, row)| {
-                row.iter().enumerate().map(move |(j, &p)| {
-                    if p == 0 {
-                        self.find_reachable_peaks((i as i64, j as i64)).len() as u64
-                    } else {
-                        0
-                    }
-                })
+            .positions(|&p| p == 0)
+            .map(|start| {
+                let trails = search::all_shortest_paths(start, |&position| {
+                    self.uphill(position).map(|next| (next, 1))
+                });
+                trails
+                    .reached()
+                    .filter(|&&position| self.peaks[position] == 9)
+                    .map(|peak| trails.count(peak))
+                    .sum::<u64>()
             })
             .sum()
     }
 
-    fn find_reachable_peaks(&self, (i, j): (i64, i64)) -> Vec<(i64, i64)> {
-        if self.peaks[i as usize][j as usize] == 9 {
-            return Vec::from([(i, j)]);
-        }
-        let curr_elevation = self.peaks[i as usize][j as usize];
-        NEIGHBORS
-            .iter()
-            .flat_map(|(di, dj)| {
-                let ni = i + di;
-                let nj = j + dj;
-                if self.is_in_mountain((ni, nj))
-                    && self.peaks[ni as usize][nj as usize] == curr_elevation + 1
-                {
-                    self.find_reachable_peaks((ni, nj))
-                } else {
-                    Vec::new()
-                }
-            })
-            .collect()
+    fn uphill(&self, position: Point) -> impl Iterator<Item = Point> + '_ {
+        let next_elevation = self.peaks[position] + 1;
+        self.peaks
+            .neighbors(position)
+            .filter(move |&next| self.peaks[next] == next_elevation)
     }
+}
 
-    fn is_in_mountain(&self, (i, j): (i64, i64)) -> bool {
-        (0..self.peaks.len() as i64).contains(&i) && (0..self.peaks[0].len() as i64).contains(&j)
+impl TryFrom<String> for Mountain {
+    type Error = ParseError;
+
+    fn try_from(value: String) -> Result<Self, ParseError> {
+        let peaks = Grid::parse_bytes(Day10::DAY, &value, |b| {
+            if b.is_ascii_digit() {
+                b - b'0'
+            } else {
+                IMPASSABLE
+            }
+        })?;
+        Ok(Mountain { peaks })
     }
 }
 
-impl From<String> for Mountain {
-    fn from(value: String) -> Self {
-        let peaks = value
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .filter_map(|c| c.to_digit(10).map(|d| d as u8))
-                    .collect()
-            })
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Puzzle = Mountain;
+
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Hoof It";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(Mountain::try_from(input)?)
+    }
+
+    fn part_1(mountain: &Self::Puzzle) -> Result<Answer> {
+        Ok(mountain.find_total_trails().into())
+    }
+
+    fn part_2(mountain: &Self::Puzzle) -> Result<Answer> {
+        Ok(mountain.find_total_distinct_trails().into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Mostly step up or down from a neighbour so the map has trails to follow
+        let mut heights: Vec<u32> = Vec::with_capacity(size * size);
+        for i in 0..size * size {
+            let left = (i % size > 0).then(|| heights[i - 1]);
+            let up = (i >= size).then(|| heights[i - size]);
+            let neighbors = [left, up].into_iter().flatten().collect::<Vec<_>>();
+            let height = if !neighbors.is_empty() && rng.chance(0.7) {
+                (rng.pick(&neighbors) + if rng.chance(0.5) { 1 } else { 9 }) % 10
+            } else {
+                rng.range(0..=9) as u32
+            };
+            heights.push(height);
+        }
+        let cells = heights
+            .iter()
+            .map(|&h| char::from_digit(h, 10).unwrap())
             .collect();
-        Mountain { peaks }
+        Grid::new(size, size, cells).to_string()
     }
 }
 
-// Elapsed time: 14248 us
-// Memory Used: 11.473633 kb
-pub fn find_total_trailheads(path: &str) -> Result<u64> {
-    let mountain = Mountain::from(get_input(path)?);
-    Ok(mountain.find_total_trails())
+// Elapsed time: 14248 us (debug build)
+// Memory Used: 11.473633 kb (debug build, whole process peak)
+// Release bench, char parsing: parse 6.9 us, peak 2.7939453 kb; solve 730.4 us, peak 1.8125 kb
+// Release bench, byte grid: parse 3.8 us, peak 2.7939453 kb; solve 740.1 us, peak 1.8125 kb
+pub fn find_total_trailheads<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day10::part_1(&Day10::parse(input)?)
 }
 
-// Elapsed time: 12470 us
-// Memory Used: 11.473633 kb
-pub fn find_total_distinct_trailheads(path: &str) -> Result<u64> {
-    let mountain = Mountain::from(get_input(path)?);
-    Ok(mountain.find_total_distinct_trails())
+// Elapsed time: 12470 us (debug build)
+// Memory Used: 11.473633 kb (debug build, whole process peak)
+// Release bench, char parsing: parse 8.1 us, peak 2.7939453 kb; solve 4091.6 us, peak 8.25 kb
+// Release bench, byte grid: parse 5.0 us, peak 2.7939453 kb; solve 4153.8 us, peak 8.25 kb
+pub fn find_total_distinct_trailheads<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day10::part_2(&Day10::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::differential;
+
+    const TEST_2: &str = "inputs/examples/day_10_2.txt";
+    const TEST_3: &str = "inputs/examples/day_10_3.txt";
+    const TEST_4: &str = "inputs/examples/day_10_4.txt";
+    const TEST_5: &str = "inputs/examples/day_10_5.txt";
+    const TEST_6: &str = "inputs/examples/day_10_6.txt";
+    const TEST_7: &str = "inputs/examples/day_10_7.txt";
+
+    #[test]
+    fn find_total_trailheads_examples() {
+        assert_eq!(find_total_trailheads(TEST).unwrap(), 36);
+        assert_eq!(find_total_trailheads(TEST_2).unwrap(), 1);
+        assert_eq!(find_total_trailheads(TEST_3).unwrap(), 2);
+        assert_eq!(find_total_trailheads(TEST_4).unwrap(), 4);
+        assert_eq!(find_total_trailheads(TEST_5).unwrap(), 3);
+    }
+
+    #[test]
+    fn find_total_distinct_trailheads_examples() {
+        assert_eq!(find_total_distinct_trailheads(TEST).unwrap(), 81);
+        assert_eq!(find_total_distinct_trailheads(TEST_4).unwrap(), 13);
+        assert_eq!(find_total_distinct_trailheads(TEST_6).unwrap(), 3);
+        assert_eq!(find_total_distinct_trailheads(TEST_7).unwrap(), 227);
+    }
+
+    fn reference_map(input: &str) -> Vec<Vec<Option<u32>>> {
+        input
+            .lines()
+            .map(|l| l.chars().map(|c| c.to_digit(10)).collect())
+            .collect()
+    }
+
+    fn reference_neighbors(
+        map: &[Vec<Option<u32>>],
+        (i, j): (usize, usize),
+    ) -> Vec<(usize, usize)> {
+        let height = map[i][j].map(|h| h + 1);
+        [
+            (i.wrapping_sub(1), j),
+            (i + 1, j),
+            (i, j.wrapping_sub(1)),
+            (i, j + 1),
+        ]
+        .into_iter()
+        .filter(|&(ni, nj)| {
+            height.is_some() && map.get(ni).and_then(|row| row.get(nj)) == Some(&height)
+        })
+        .collect()
+    }
+
+    fn reference_part_1(input: &str) -> usize {
+        let map = reference_map(input);
+        let mut score = 0;
+        for (i, row) in map.iter().enumerate() {
+            for (j, &height) in row.iter().enumerate() {
+                if height == Some(0) {
+                    let mut reached = HashSet::from([(i, j)]);
+                    let mut queue = vec![(i, j)];
+                    while let Some(cell) = queue.pop() {
+                        for next in reference_neighbors(&map, cell) {
+                            if reached.insert(next) {
+                                queue.push(next);
+                            }
+                        }
+                    }
+                    score += reached
+                        .iter()
+                        .filter(|&&(i, j)| map[i][j] == Some(9))
+                        .count();
+                }
+            }
+        }
+        score
+    }
+
+    fn reference_part_2(input: &str) -> u64 {
+        // Number of trails from each cell to any peak, filled in from the peaks downwards
+        let map = reference_map(input);
+        let mut trails = vec![vec![0u64; map[0].len()]; map.len()];
+        for height in (0..=9).rev() {
+            for i in 0..map.len() {
+                for j in 0..map[i].len() {
+                    if map[i][j] == Some(height) {
+                        trails[i][j] = if height == 9 {
+                            1
+                        } else {
+                            reference_neighbors(&map, (i, j))
+                                .iter()
+                                .map(|&(ni, nj)| trails[ni][nj])
+                                .sum()
+                        };
+                    }
+                }
+            }
+        }
+        (0..map.len())
+            .flat_map(|i| (0..map[i].len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| map[i][j] == Some(0))
+            .map(|(i, j)| trails[i][j])
+            .sum()
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day10, 8, reference_part_1, reference_part_2);
+    }
 }
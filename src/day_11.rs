@@ -1,20 +1,23 @@
 use std::collections::HashMap;
 
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    input::{get_input, Input},
+    parse,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_11.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_11.txt";
 
-struct Stones {
+pub struct Stones {
     stones: Vec<u64>,
 }
 
 impl Stones {
-    fn new() -> Self {
-        Stones { stones: Vec::new() }
-    }
-
     fn count_stones_after_blinks(&self, n: u64) -> u64 {
         let mut cache = HashMap::new();
         self.stones
@@ -33,7 +36,7 @@ impl Stones {
         let count = if *stone == 0 {
             self.blink_stone(&1, n - 1, cache)
         } else if stone.ilog10() % 2 == 1 {
-            let half = 10u64.pow((stone.ilog10() + 1) / 2);
+            let half = 10u64.pow(stone.ilog10().div_ceil(2));
             self.blink_stone(&(stone / half), n - 1, cache)
                 + self.blink_stone(&(stone % half), n - 1, cache)
         } else {
@@ -44,14 +47,42 @@ impl Stones {
     }
 }
 
-impl From<String> for Stones {
-    fn from(s: String) -> Self {
-        let stones = s
-            .trim()
-            .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
-        Stones { stones }
+impl TryFrom<String> for Stones {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        let stones = parse::words(Day11::DAY, &s, &s).collect::<Result<_, _>>()?;
+        Ok(Stones { stones })
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Puzzle = Stones;
+
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(Stones::try_from(input)?)
+    }
+
+    fn part_1(stones: &Self::Puzzle) -> Result<Answer> {
+        Ok(stones.count_stones_after_blinks(25).into())
+    }
+
+    fn part_2(stones: &Self::Puzzle) -> Result<Answer> {
+        Ok(stones.count_stones_after_blinks(75).into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| rng.range(0..=999_999).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 }
 
@@ -62,8 +93,72 @@ impl From<String> for Stones {
 // Part 2
 // Elapsed time: 169056 us
 // Memory Used: 9603.93 kb
-pub fn get_total_stones_after_blinks(path: &str, n: u64) -> Result<u64> {
-    let input = get_input(path)?;
-    let stones = Stones::from(input);
-    Ok(stones.count_stones_after_blinks(n))
+pub fn get_total_stones_after_blinks<'a>(input: impl Into<Input<'a>>, n: u64) -> Result<Answer> {
+    let stones = Day11::parse(get_input(input)?)?;
+    Ok(stones.count_stones_after_blinks(n).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    const TEST_2: &str = "inputs/examples/day_11_2.txt";
+
+    #[test]
+    fn get_total_stones_after_blinks_examples() {
+        assert_eq!(get_total_stones_after_blinks(TEST_2, 1).unwrap(), 7);
+        assert_eq!(get_total_stones_after_blinks(TEST, 6).unwrap(), 22);
+        assert_eq!(get_total_stones_after_blinks(TEST, 25).unwrap(), 55312);
+    }
+
+    fn reference_blink(stone: u64) -> Vec<u64> {
+        let digits = (1..).find(|&d| stone < 10u64.pow(d)).unwrap();
+        if stone == 0 {
+            vec![1]
+        } else if digits.is_multiple_of(2) {
+            let half = 10u64.pow(digits / 2);
+            vec![stone / half, stone % half]
+        } else {
+            vec![stone * 2024]
+        }
+    }
+
+    fn reference_stones(input: &str) -> Vec<u64> {
+        input
+            .split_whitespace()
+            .map(|n| n.parse().unwrap())
+            .collect()
+    }
+
+    fn reference_part_1(input: &str) -> usize {
+        let mut stones = reference_stones(input);
+        for _ in 0..25 {
+            stones = stones.into_iter().flat_map(reference_blink).collect();
+        }
+        stones.len()
+    }
+
+    fn reference_part_2(input: &str) -> u64 {
+        // Stones never affect each other, so equal stones can be blinked together
+        let mut stones = HashMap::new();
+        for stone in reference_stones(input) {
+            *stones.entry(stone).or_insert(0) += 1;
+        }
+        for _ in 0..75 {
+            let mut next = HashMap::new();
+            for (stone, count) in stones {
+                for s in reference_blink(stone) {
+                    *next.entry(s).or_insert(0) += count;
+                }
+            }
+            stones = next;
+        }
+        stones.values().sum()
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day11, 1, reference_part_1, reference_part_2);
+    }
 }
@@ -1,115 +1,248 @@
-use std::collections::HashSet;
-
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    bitset::PointSet,
+    error::ParseError,
+    generate::Rng,
+    grid::Grid,
+    input::{get_input, Input},
+    point::{Direction, Point},
+    search,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_12.txt";
-pub const TEST: &str = "inputs/test.txt";
-
-const NEIGHBORS: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+pub const TEST: &str = "inputs/examples/day_12.txt";
 
-struct FarmLand {
-    land: Vec<Vec<char>>,
+pub struct FarmLand {
+    land: Grid<u8>,
 }
 
 impl FarmLand {
-    fn new() -> Self {
-        FarmLand { land: Vec::new() }
+    fn find_fence_price(&self, bulk: bool) -> u64 {
+        let mut visited = PointSet::for_grid(&self.land);
+        let mut cost = 0;
+        for (position, _c) in self.land.iter() {
+            if !visited.contains(position) {
+                let region = self.find_region(position, &mut visited);
+                let (region_area, region_perimeter, region_sides) =
+                    self.find_region_area_perimeter_side(&region);
+                cost += region_area * (if bulk { region_sides } else { region_perimeter });
+            }
+        }
+        cost
     }
 
-    fn find_fence_price(&self, bulk: bool) -> u64 {
-        let mut visited = HashSet::new();
-        self.land
-            .iter()
-            .enumerate()
-            .filter_map(|(i, row)| {
-                let mut cost = 0;
-                for (j, _c) in row.iter().enumerate() {
-                    if visited.insert((i as i64, j as i64)) {
-                        let (region_area, region_perimeter, region_sides) = self
-                            .find_region_area_perimeter_side((i as i64, j as i64), &mut visited);
-                        cost += region_area * (if bulk { region_sides } else { region_perimeter });
-                    }
-                }
-                Some(cost)
-            })
-            .sum()
+    /// Flood fills the region around `start`, marking each of its cells in `visited`.
+    fn find_region(&self, start: Point, visited: &mut PointSet) -> Vec<Point> {
+        search::bfs_with(start, visited, |&position| {
+            self.land
+                .neighbors(position)
+                .filter(move |&next| self.land[next] == self.land[position])
+        })
+        .map(|(position, _)| position)
+        .collect()
     }
 
-    fn find_region_area_perimeter_side(
-        &self,
-        start: (i64, i64),
-        visited: &mut HashSet<(i64, i64)>,
-    ) -> (u64, u64, u64) {
-        let mut area = 0;
+    fn find_region_area_perimeter_side(&self, region: &[Point]) -> (u64, u64, u64) {
         let mut perimeter = 0;
         let mut sides = 0;
-        let region_identifier = self.land[start.0 as usize][start.1 as usize];
-        let mut queue = vec![start];
-        while !queue.is_empty() {
-            let (i, j) = queue.pop().unwrap();
-            area += 1;
-            NEIGHBORS
-                .iter()
-                .zip(NEIGHBORS.iter().cycle().skip(1))
-                .for_each(|(&(di, dj), &(di2, dj2))| {
-                    let (ni, nj) = (i + di, j + dj);
-                    let (ni2, nj2) = (i + di2, j + dj2);
-                    let (ni3, nj3) = (i + di + di2, j + dj + dj2);
-                    let is_in_map = self.is_in_map((ni, nj));
-                    let is_in_map2 = self.is_in_map((ni2, nj2));
-                    let is_in_map3 = self.is_in_map((ni3, nj3));
-                    if !is_in_map || self.land[ni as usize][nj as usize] != region_identifier {
-                        perimeter += 1;
-                        if !is_in_map2 || self.land[ni2 as usize][nj2 as usize] != region_identifier
-                        {
-                            sides += 1;
-                        }
-                    } else {
-                        if visited.insert((ni, nj)) {
-                            queue.push((ni, nj));
-                        }
-                        if is_in_map2
-                            && self.land[ni2 as usize][nj2 as usize] == region_identifier
-                            && is_in_map3
-                            && self.land[ni3 as usize][nj3 as usize] != region_identifier
-                        {
-                            sides += 1;
-                        }
+        let region_identifier = self.land[region[0]];
+        let in_region = |position| self.land.get(position) == Some(&region_identifier);
+        for &position in region {
+            Direction::CARDINAL.iter().for_each(|&direction| {
+                let next = position + direction;
+                let side = position + direction.turn_right();
+                let corner = next + direction.turn_right();
+                if !in_region(next) {
+                    perimeter += 1;
+                    if !in_region(side) {
+                        sides += 1;
                     }
-                });
+                } else if in_region(side) && !in_region(corner) {
+                    sides += 1;
+                }
+            });
         }
 
-        (area, perimeter, sides)
+        (region.len() as u64, perimeter, sides)
     }
+}
 
-    fn is_in_map(&self, (i, j): (i64, i64)) -> bool {
-        (0..self.land.len() as i64).contains(&i)
-            && (0..self.land[i as usize].len() as i64).contains(&j)
+impl TryFrom<String> for FarmLand {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        let land = Grid::parse_bytes(Day12::DAY, &s, |b| b)?;
+        Ok(FarmLand { land })
     }
 }
 
-impl From<String> for FarmLand {
-    fn from(s: String) -> Self {
-        let land = s
-            .trim()
-            .lines()
-            .map(|line| line.chars().collect())
-            .collect();
-        FarmLand { land }
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Puzzle = FarmLand;
+
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Garden Groups";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(FarmLand::try_from(input)?)
+    }
+
+    fn part_1(farm_land: &Self::Puzzle) -> Result<Answer> {
+        Ok(farm_land.find_fence_price(false).into())
+    }
+
+    fn part_2(farm_land: &Self::Puzzle) -> Result<Answer> {
+        Ok(farm_land.find_fence_price(true).into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Mostly copy a neighbouring plant so the plots form regions
+        let mut plants: Vec<char> = Vec::with_capacity(size * size);
+        for i in 0..size * size {
+            let left = (i % size > 0).then(|| plants[i - 1]);
+            let up = (i >= size).then(|| plants[i - size]);
+            let neighbors = [left, up].into_iter().flatten().collect::<Vec<_>>();
+            let plant = if !neighbors.is_empty() && rng.chance(0.85) {
+                *rng.pick(&neighbors)
+            } else {
+                (b'A' + rng.range(0..=25) as u8) as char
+            };
+            plants.push(plant);
+        }
+        Grid::new(size, size, plants).to_string()
     }
 }
 
-// Elapsed time: 48853 us
-// Memory Used: 904.7422 kb
-pub fn find_fencing_price(path: &str) -> Result<u64> {
-    let farm_land = FarmLand::from(get_input(path)?);
-    Ok(farm_land.find_fence_price(false))
+// Elapsed time: 48853 us (debug build)
+// Memory Used: 904.7422 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 53.0 us, peak 77.10547 kb; solve 7755.0 us, peak 817.03125 kb
+// Release bench, byte grid: parse 12.4 us, peak 19.276367 kb; solve 8152.2 us, peak 817.03125 kb
+// Release bench, bitsets: parse 6.1 us, peak 19.276367 kb; solve 1342.5 us, peak 14.7734375 kb
+pub fn find_fencing_price<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day12::part_1(&Day12::parse(input)?)
 }
 
-// Elapsed time: 49986 us
-// Memory Used: 904.7422 kb
-pub fn find_bulk_fencing_price(path: &str) -> Result<u64> {
-    let farm_land = FarmLand::from(get_input(path)?);
-    Ok(farm_land.find_fence_price(true))
+// Elapsed time: 49986 us (debug build)
+// Memory Used: 904.7422 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 60.9 us, peak 77.10547 kb; solve 8899.6 us, peak 817.03125 kb
+// Release bench, byte grid: parse 12.6 us, peak 19.276367 kb; solve 8308.3 us, peak 817.03125 kb
+// Release bench, bitsets: parse 6.1 us, peak 19.276367 kb; solve 1325.1 us, peak 14.7734375 kb
+pub fn find_bulk_fencing_price<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day12::part_2(&Day12::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::differential;
+
+    const TEST_2: &str = "inputs/examples/day_12_2.txt";
+    const TEST_3: &str = "inputs/examples/day_12_3.txt";
+    const TEST_4: &str = "inputs/examples/day_12_4.txt";
+    const TEST_5: &str = "inputs/examples/day_12_5.txt";
+
+    #[test]
+    fn find_fencing_price_examples() {
+        assert_eq!(find_fencing_price(TEST).unwrap(), 1930);
+        assert_eq!(find_fencing_price(TEST_2).unwrap(), 140);
+        assert_eq!(find_fencing_price(TEST_3).unwrap(), 772);
+    }
+
+    #[test]
+    fn find_bulk_fencing_price_examples() {
+        assert_eq!(find_bulk_fencing_price(TEST).unwrap(), 1206);
+        assert_eq!(find_bulk_fencing_price(TEST_2).unwrap(), 80);
+        assert_eq!(find_bulk_fencing_price(TEST_3).unwrap(), 436);
+        assert_eq!(find_bulk_fencing_price(TEST_4).unwrap(), 236);
+        assert_eq!(find_bulk_fencing_price(TEST_5).unwrap(), 368);
+    }
+
+    #[test]
+    fn solve_both_example() {
+        let answers = (1930u64.into(), 1206u64.into());
+        assert_eq!(Day12::solve_both(TEST).unwrap(), answers);
+    }
+
+    /// Every region as the set of its cells.
+    fn reference_regions(input: &str) -> Vec<HashSet<(i64, i64)>> {
+        let grid = input
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect::<Vec<Vec<_>>>();
+        let plant = |(i, j): (i64, i64)| {
+            grid.get(usize::try_from(i).ok()?)?
+                .get(usize::try_from(j).ok()?)
+                .copied()
+        };
+        let mut seen = HashSet::new();
+        let mut regions = Vec::new();
+        for i in 0..grid.len() as i64 {
+            for j in 0..grid[0].len() as i64 {
+                if seen.contains(&(i, j)) {
+                    continue;
+                }
+                let mut region = HashSet::from([(i, j)]);
+                let mut queue = vec![(i, j)];
+                while let Some((ci, cj)) = queue.pop() {
+                    for next in [(ci - 1, cj), (ci + 1, cj), (ci, cj - 1), (ci, cj + 1)] {
+                        if plant(next) == plant((i, j)) && region.insert(next) {
+                            queue.push(next);
+                        }
+                    }
+                }
+                seen.extend(region.iter().copied());
+                regions.push(region);
+            }
+        }
+        regions
+    }
+
+    /// Sides of a region's cells that face another region, as `(cell, direction)` pairs.
+    fn reference_fences(region: &HashSet<(i64, i64)>) -> HashSet<((i64, i64), (i64, i64))> {
+        region
+            .iter()
+            .flat_map(|&(i, j)| {
+                [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .into_iter()
+                    .filter(move |(di, dj)| !region.contains(&(i + di, j + dj)))
+                    .map(move |d| ((i, j), d))
+            })
+            .collect()
+    }
+
+    fn reference_part_1(input: &str) -> usize {
+        reference_regions(input)
+            .iter()
+            .map(|region| region.len() * reference_fences(region).len())
+            .sum()
+    }
+
+    fn reference_part_2(input: &str) -> usize {
+        reference_regions(input)
+            .iter()
+            .map(|region| {
+                let fences = reference_fences(region);
+                // A side is a run of fences along one line, counted at the fence where it starts
+                let sides = fences
+                    .iter()
+                    .filter(|&&((i, j), (di, dj))| !fences.contains(&((i + dj, j + di), (di, dj))))
+                    .count();
+                region.len() * sides
+            })
+            .sum()
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day12, 8, reference_part_1, reference_part_2);
+    }
 }
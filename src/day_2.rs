@@ -1,18 +1,21 @@
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    input::{get_input, Input},
+    parse,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_2.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_2.txt";
 
-struct Levels {
+pub struct Levels {
     levels: Vec<Vec<i64>>,
 }
 
 impl Levels {
-    fn new() -> Levels {
-        Levels { levels: Vec::new() }
-    }
-
     fn find_safe_reports(&self, with_dampaner: bool) -> u64 {
         self.levels.iter().fold(0, |acc, level| {
             let is_safe = Self::is_safe(level);
@@ -30,7 +33,7 @@ impl Levels {
         })
     }
 
-    fn is_safe(level: &Vec<i64>) -> bool {
+    fn is_safe(level: &[i64]) -> bool {
         let differences = level.windows(2).map(|w| w[1] - w[0]);
         let is_consistent =
             differences.clone().all(|d| d < 0) || differences.clone().all(|d| d > 0);
@@ -39,17 +42,15 @@ impl Levels {
     }
 }
 
-impl From<String> for Levels {
-    fn from(s: String) -> Levels {
+impl TryFrom<String> for Levels {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Levels, ParseError> {
         let levels = s
             .lines()
-            .map(|l| {
-                l.split_whitespace()
-                    .map(|n| n.parse::<i64>().unwrap())
-                    .collect()
-            })
-            .collect();
-        Levels { levels }
+            .map(|l| parse::words(Day2::DAY, &s, l).collect())
+            .collect::<Result<_, _>>()?;
+        Ok(Levels { levels })
     }
 }
 
@@ -59,18 +60,112 @@ impl From<Vec<Vec<i64>>> for Levels {
     }
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Puzzle = Levels;
+
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(Levels::try_from(input)?)
+    }
+
+    fn part_1(levels: &Self::Puzzle) -> Result<Answer> {
+        Ok(levels.find_safe_reports(false).into())
+    }
+
+    fn part_2(levels: &Self::Puzzle) -> Result<Answer> {
+        Ok(levels.find_safe_reports(true).into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| {
+                let direction = if rng.chance(0.5) { 1 } else { -1 };
+                let mut level = rng.range(10..=90) as i64;
+                let mut levels = Vec::new();
+                for _ in 0..rng.range(5..=8) {
+                    levels.push(level.to_string());
+                    level += direction * rng.range(1..=3) as i64;
+                    if rng.chance(0.1) {
+                        level += rng.range(0..=8) as i64 - 4;
+                    }
+                    level = level.max(1);
+                }
+                levels.join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 // Elapsed time: 1739 us
 // Memory Used: 109.03613 kb
-pub fn get_safe_reports(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let levels = Levels::from(input);
-    Ok(levels.find_safe_reports(false))
+pub fn get_safe_reports<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day2::part_1(&Day2::parse(input)?)
 }
 
 // Elapsed time: 2996 us
 // Memory Used: 109.03613 kb
-pub fn get_safe_reports_with_dampaner(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let levels = Levels::from(input);
-    Ok(levels.find_safe_reports(true))
+pub fn get_safe_reports_with_dampaner<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day2::part_2(&Day2::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    #[test]
+    fn get_safe_reports_example() {
+        assert_eq!(get_safe_reports(TEST).unwrap(), 2);
+    }
+
+    #[test]
+    fn get_safe_reports_with_dampaner_example() {
+        assert_eq!(get_safe_reports_with_dampaner(TEST).unwrap(), 4);
+    }
+
+    fn reference_reports(input: &str) -> Vec<Vec<i64>> {
+        input
+            .lines()
+            .map(|l| l.split_whitespace().map(|n| n.parse().unwrap()).collect())
+            .collect()
+    }
+
+    fn reference_is_safe(levels: &[i64]) -> bool {
+        let steps = levels.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+        steps.iter().all(|s| (1..=3).contains(s)) || steps.iter().all(|s| (-3..=-1).contains(s))
+    }
+
+    fn reference_part_1(input: &str) -> usize {
+        reference_reports(input)
+            .iter()
+            .filter(|levels| reference_is_safe(levels))
+            .count()
+    }
+
+    fn reference_part_2(input: &str) -> usize {
+        reference_reports(input)
+            .iter()
+            .filter(|levels| {
+                (0..levels.len()).any(|i| {
+                    let mut dampened = levels.to_vec();
+                    dampened.remove(i);
+                    reference_is_safe(&dampened)
+                })
+            })
+            .count()
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day2, 20, reference_part_1, reference_part_2);
+    }
 }
@@ -1,45 +1,189 @@
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    input::{get_input, Input},
+    solution::Solution,
+};
 use anyhow::Result;
 use regex::Regex;
 
 pub const INPUT: &str = "inputs/day_3.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_3.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Instruction {
+    Do,
+    Dont,
+    Mul(i64, i64),
+}
+
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    fn sum_multiplications(&self, conditional: bool) -> i64 {
+        let mut ignore = false;
+        self.instructions
+            .iter()
+            .map(|instruction| {
+                match instruction {
+                    Instruction::Do => ignore = false,
+                    Instruction::Dont => ignore = conditional,
+                    Instruction::Mul(first, second) if !ignore => return first * second,
+                    Instruction::Mul(_, _) => {}
+                }
+                0
+            })
+            .sum()
+    }
+}
+
+impl TryFrom<String> for Program {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        let instructions_regex =
+            Regex::new(r"do\(\)|don\'t\(\)|mul\((?<first>\d+),(?<second>\d+)\)").unwrap();
+        let instructions = instructions_regex
+            .captures_iter(&s)
+            .map(|cap| match cap.get(0).unwrap().as_str() {
+                "do()" => Ok(Instruction::Do),
+                "don't()" => Ok(Instruction::Dont),
+                _ => {
+                    let parse = |name| {
+                        let n = cap.name(name).unwrap().as_str();
+                        n.parse::<i64>()
+                            .map_err(|e| ParseError::at(Day3::DAY, &s, n, e))
+                    };
+                    Ok(Instruction::Mul(parse("first")?, parse("second")?))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { instructions })
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Puzzle = Program;
+
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(Program::try_from(input)?)
+    }
+
+    fn part_1(program: &Self::Puzzle) -> Result<Answer> {
+        Ok(program.sum_multiplications(false).into())
+    }
+
+    fn part_2(program: &Self::Puzzle) -> Result<Answer> {
+        Ok(program.sum_multiplications(true).into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const CORRUPTED: [&str; 8] = [
+            "mul(4*",
+            "mul[3,7]",
+            "?(12,34)",
+            "select()",
+            "from()",
+            "when()",
+            " mul ( 2 , 4 )",
+            "%&!@^",
+        ];
+        (0..size)
+            .map(|i| {
+                let instruction = match rng.range(0..=9) {
+                    0 => String::from("do()"),
+                    1 => String::from("don't()"),
+                    2..=5 => format!("mul({},{})", rng.range(1..=999), rng.range(1..=999)),
+                    _ => rng.pick(&CORRUPTED).to_string(),
+                };
+                if i > 0 && i % 100 == 0 {
+                    format!("\n{}", instruction)
+                } else {
+                    instruction
+                }
+            })
+            .collect()
+    }
+}
 
 // Elapsed time: 5582 us
 // Memory Used: 354.2256 kb
-pub fn calculate_corrupted_mul_instructions(path: &str) -> Result<i64> {
-    let input = get_input(path)?;
-    let instructions_regex = Regex::new(r"mul\((?<first>\d+),(?<second>\d+)\)").unwrap();
-    Ok(instructions_regex
-        .captures_iter(&input)
-        .map(|cap| {
-            let first = cap["first"].parse::<i64>().unwrap();
-            let second = cap["second"].parse::<i64>().unwrap();
-            first * second
-        })
-        .sum())
+pub fn calculate_corrupted_mul_instructions<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day3::part_1(&Day3::parse(input)?)
 }
 
 // Elapsed time: 7897 us
 // Memory Used: 359.7959 kb
-pub fn calculate_corrupted_mul_do_instructions(path: &str) -> Result<i64> {
-    let input = get_input(path)?;
-    let instructions_regex =
-        Regex::new(r"do\(\)|don\'t\(\)|mul\((?<first>\d+),(?<second>\d+)\)").unwrap();
-    let mut ignore = false;
-    Ok(instructions_regex
-        .captures_iter(&input)
-        .map(|cap| {
-            if cap.get(0).unwrap().as_str() == "do()" {
-                ignore = false;
-            } else if cap.get(0).unwrap().as_str() == "don't()" {
-                ignore = true;
-            } else if !ignore {
-                let first = cap["first"].parse::<i64>().unwrap();
-                let second = cap["second"].parse::<i64>().unwrap();
-                return first * second;
+pub fn calculate_corrupted_mul_do_instructions<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day3::part_2(&Day3::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    const TEST_2: &str = "inputs/examples/day_3_2.txt";
+
+    #[test]
+    fn calculate_corrupted_mul_instructions_example() {
+        assert_eq!(calculate_corrupted_mul_instructions(TEST).unwrap(), 161);
+    }
+
+    #[test]
+    fn calculate_corrupted_mul_do_instructions_example() {
+        assert_eq!(calculate_corrupted_mul_do_instructions(TEST_2).unwrap(), 48);
+    }
+
+    /// Reads the digits of `mul(a,b)` at the start of `s`.
+    fn reference_mul(s: &str) -> Option<i64> {
+        let (a, rest) = s.strip_prefix("mul(")?.split_once(',')?;
+        let (b, _) = rest.split_once(')')?;
+        let is_number = |n: &str| !n.is_empty() && n.bytes().all(|c| c.is_ascii_digit());
+        (is_number(a) && is_number(b))
+            .then(|| a.parse::<i64>().unwrap() * b.parse::<i64>().unwrap())
+    }
+
+    fn reference_sum(input: &str, conditional: bool) -> i64 {
+        let mut enabled = true;
+        let mut sum = 0;
+        for i in 0..input.len() {
+            let rest = &input[i..];
+            if rest.starts_with("do()") {
+                enabled = true;
+            } else if rest.starts_with("don't()") {
+                enabled = false;
+            } else if let Some(product) = reference_mul(rest) {
+                if enabled || !conditional {
+                    sum += product;
+                }
             }
-            0
-        })
-        .sum())
+        }
+        sum
+    }
+
+    fn reference_part_1(input: &str) -> i64 {
+        reference_sum(input, false)
+    }
+
+    fn reference_part_2(input: &str) -> i64 {
+        reference_sum(input, true)
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day3, 30, reference_part_1, reference_part_2);
+    }
 }
@@ -1,131 +1,199 @@
 use std::collections::HashSet;
 
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    grid::Grid,
+    input::{get_input, Input},
+    point::{Direction, Point},
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_4.txt";
-pub const TEST: &str = "inputs/test.txt";
-
-const NEIGHBORS: [(i64, i64); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-const DIAGNOL_NEIGHBORS: [(i64, i64); 4] = [(-1, -1), (1, 1), (1, -1), (-1, 1)];
-
-const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
-const MAS: [char; 2] = ['M', 'S'];
-
-struct WordSearch {
-    grid: Vec<Vec<char>>,
+pub const TEST: &str = "inputs/examples/day_4.txt";
+
+const XMAS: &[u8; 4] = b"XMAS";
+const MAS: &[u8; 2] = b"MS";
+
+pub struct WordSearch {
+    grid: Grid<u8>,
 }
 
 impl WordSearch {
-    pub fn new() -> Self {
-        Self { grid: Vec::new() }
-    }
-
     pub fn find_xmas_count(&self) -> u64 {
         self.grid
-            .iter()
-            .enumerate()
-            .flat_map(|(i, row)| {
-                row.iter().enumerate().map(move |(j, c)| {
-                    if *c != 'X' {
-                        return 0;
-                    }
-                    NEIGHBORS
-                        .iter()
-                        .map(move |direction| {
-                            if self.check_xmas_match(&i, &j, *direction) {
-                                1
-                            } else {
-                                0
-                            }
-                        })
-                        .sum()
-                })
+            .positions(|&c| c == b'X')
+            .map(|position| {
+                Direction::ALL
+                    .iter()
+                    .filter(|&&direction| self.check_xmas_match(position, direction))
+                    .count() as u64
             })
             .sum()
     }
 
     pub fn find_xmas_cross_count(&self) -> u64 {
         self.grid
+            .positions(|&c| c == b'A')
+            .filter(|position| self.check_xmas_cross_match(*position))
+            .count() as u64
+    }
+
+    fn check_xmas_match(&self, position: Point, direction: Direction) -> bool {
+        self.grid
+            .line(position, direction.delta())
+            .take(XMAS.len())
+            .eq(XMAS.iter())
+    }
+
+    fn check_xmas_cross_match(&self, position: Point) -> bool {
+        [Direction::NorthWest, Direction::NorthEast]
             .iter()
-            .enumerate()
-            .flat_map(|(i, row)| {
-                row.iter().enumerate().map(move |(j, c)| {
-                    if *c != 'A' {
-                        0
-                    } else if self.check_xmas_cross_match(&i, &j) {
-                        1
-                    } else {
-                        0
-                    }
-                })
+            .all(|&corner| {
+                let letters = [corner, corner.opposite()]
+                    .iter()
+                    .filter_map(|&direction| self.grid.get(position + direction))
+                    .collect::<HashSet<_>>();
+                MAS.iter().all(|c| letters.contains(c))
             })
-            .sum()
     }
+}
 
-    fn check_xmas_match(&self, i: &usize, j: &usize, direction: (i64, i64)) -> bool {
-        XMAS.iter().enumerate().all(|(ind, c)| {
-            let new_i = *i as i64 + direction.0 * ind as i64;
-            let new_j = *j as i64 + direction.1 * ind as i64;
-            if (0..self.grid.len()).contains(&(new_i as usize))
-                && (0..self.grid[new_i as usize].len()).contains(&(new_j as usize))
-            {
-                self.grid[new_i as usize][new_j as usize] == *c
-            } else {
-                false
-            }
-        })
-    }
-
-    fn check_xmas_cross_match(&self, i: &usize, j: &usize) -> bool {
-        DIAGNOL_NEIGHBORS.chunks(2).all(|corners| {
-            let letters = corners
-                .iter()
-                .filter_map(|(d_i, d_j)| {
-                    let new_i = *i as i64 + d_i;
-                    let new_j = *j as i64 + d_j;
-                    if (0..self.grid.len()).contains(&(new_i as usize))
-                        && (0..self.grid[new_i as usize].len()).contains(&(new_j as usize))
-                    {
-                        Some(self.grid[new_i as usize][new_j as usize])
-                    } else {
-                        None
-                    }
-                })
-                .collect::<HashSet<_>>();
-            MAS.iter().all(|c| letters.contains(c))
-        })
+impl TryFrom<String> for WordSearch {
+    type Error = ParseError;
+
+    fn try_from(input: String) -> Result<Self, ParseError> {
+        let grid = Grid::parse_bytes(Day4::DAY, &input, |b| b)?;
+        Ok(Self { grid })
     }
 }
 
-impl From<String> for WordSearch {
-    fn from(input: String) -> Self {
-        let grid = input.lines().map(|line| line.chars().collect()).collect();
-        Self { grid }
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Puzzle = WordSearch;
+
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(WordSearch::try_from(input)?)
+    }
+
+    fn part_1(word_search: &Self::Puzzle) -> Result<Answer> {
+        Ok(word_search.find_xmas_count().into())
+    }
+
+    fn part_2(word_search: &Self::Puzzle) -> Result<Answer> {
+        Ok(word_search.find_xmas_cross_count().into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let letters = (0..size * size)
+            .map(|_| char::from(*rng.pick(XMAS)))
+            .collect();
+        Grid::new(size, size, letters).to_string()
     }
 }
 
-// Elapsed time: 7550 us
-// Memory Used: 108.143555 kb
-pub fn find_xmas_wordsearch(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let word_search = WordSearch::from(input);
-    Ok(word_search.find_xmas_count())
+// Elapsed time: 7550 us (debug build)
+// Memory Used: 108.143555 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 53.1 us, peak 77.10547 kb; solve 621.9 us, peak 0 kb
+// Release bench, byte grid: parse 5.2 us, peak 19.276367 kb; solve 382.2 us, peak 0 kb
+pub fn find_xmas_wordsearch<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day4::part_1(&Day4::parse(input)?)
 }
 
-// Elapsed time: 10769 us
-// Memory Used: 108.143555 kb
-pub fn find_xmas_cross_wordsearch(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let word_search = WordSearch::from(input);
-    Ok(word_search.find_xmas_cross_count())
+// Elapsed time: 10769 us (debug build)
+// Memory Used: 108.143555 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 54.1 us, peak 77.10547 kb; solve 1378.3 us, peak 0.05078125 kb
+// Release bench, byte grid: parse 5.1 us, peak 19.276367 kb; solve 861.0 us, peak 0.05078125 kb
+pub fn find_xmas_cross_wordsearch<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day4::part_2(&Day4::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    const TEST_2: &str = "inputs/examples/day_4_2.txt";
+
+    #[test]
+    fn find_xmas_wordsearch_examples() {
+        assert_eq!(find_xmas_wordsearch(TEST).unwrap(), 18);
+        assert_eq!(find_xmas_wordsearch(TEST_2).unwrap(), 4);
+    }
+
+    #[test]
+    fn find_xmas_cross_wordsearch_example() {
+        assert_eq!(find_xmas_cross_wordsearch(TEST).unwrap(), 9);
+    }
+
+    #[test]
+    fn solve_both_matches_parts() {
+        let answers = (18u64.into(), 9u64.into());
+        assert_eq!(Day4::solve_both(TEST).unwrap(), answers);
+        let word_search = Day4::load(TEST_2).unwrap();
+        assert_eq!(Day4::part_1(&word_search).unwrap(), 4);
+        assert_eq!(Day4::part_2(&word_search).unwrap(), 0);
+    }
+
+    fn reference_letter(grid: &[Vec<char>], i: i64, j: i64) -> Option<char> {
+        grid.get(usize::try_from(i).ok()?)?
+            .get(usize::try_from(j).ok()?)
+            .copied()
+    }
+
+    fn reference_part_1(input: &str) -> usize {
+        let grid = input
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect::<Vec<Vec<_>>>();
+        let mut count = 0;
+        for i in 0..grid.len() as i64 {
+            for j in 0..grid[0].len() as i64 {
+                for (di, dj) in (-1..=1).flat_map(|di| (-1..=1).map(move |dj| (di, dj))) {
+                    let word = (0..4)
+                        .filter_map(|k| reference_letter(&grid, i + di * k, j + dj * k))
+                        .collect::<String>();
+                    if (di, dj) != (0, 0) && word == "XMAS" {
+                        count += 1;
+                    }
+                }
+            }
+        }
+        count
+    }
+
+    fn reference_part_2(input: &str) -> usize {
+        let grid = input
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect::<Vec<Vec<_>>>();
+        let mut count = 0;
+        for i in 0..grid.len() as i64 {
+            for j in 0..grid[0].len() as i64 {
+                let letter = |di, dj| reference_letter(&grid, i + di, j + dj).unwrap_or('.');
+                let diagonal = [letter(-1, -1), letter(0, 0), letter(1, 1)];
+                let anti_diagonal = [letter(-1, 1), letter(0, 0), letter(1, -1)];
+                let is_mas = |word: [char; 3]| word == ['M', 'A', 'S'] || word == ['S', 'A', 'M'];
+                if is_mas(diagonal) && is_mas(anti_diagonal) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day4, 8, reference_part_1, reference_part_2);
+    }
 }
@@ -1,12 +1,19 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    input::{get_input, Input},
+    parse,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_5.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_5.txt";
 
-struct SafetyManual {
+pub struct SafetyManual {
     page_ordering: HashMap<u64, HashSet<u64>>,
     updates: Vec<Vec<u64>>,
 }
@@ -53,10 +60,10 @@ impl SafetyManual {
                 loop {
                     let mut seen = HashMap::new();
                     let valid = curr_update.clone().iter().enumerate().all(|(ind, page)| {
-                        if let Some(rules) = self.page_ordering.get(&page) {
+                        if let Some(rules) = self.page_ordering.get(page) {
                             let vals = seen.keys().cloned().collect::<HashSet<_>>();
                             let invalid_pages = vals.intersection(rules).collect::<HashSet<_>>();
-                            if invalid_pages.len() == 0 {
+                            if invalid_pages.is_empty() {
                                 seen.insert(*page, ind);
                                 return true;
                             } else {
@@ -81,38 +88,168 @@ impl SafetyManual {
     }
 }
 
-impl From<String> for SafetyManual {
-    fn from(s: String) -> Self {
-        let (ordering_rules, updates) = s.split_once("\n\n").unwrap();
-        let page_ordering = ordering_rules
-            .lines()
-            .fold(HashMap::new(), |mut map, rule| {
-                let (page, rule) = rule.split_once("|").unwrap();
-                let page = page.parse().unwrap();
-                let rule = rule.parse().unwrap();
-                map.entry(page).or_insert_with(HashSet::new).insert(rule);
-                map
-            });
+impl TryFrom<String> for SafetyManual {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        let mut sections = parse::sections(&s);
+        let (Some(ordering_rules), Some(updates), None) =
+            (sections.next(), sections.next(), sections.next())
+        else {
+            return Err(ParseError::at(
+                Day5::DAY,
+                &s,
+                &s[s.len()..],
+                "expected the rules and the updates separated by a blank line",
+            ));
+        };
+        let mut page_ordering: HashMap<u64, HashSet<u64>> = HashMap::new();
+        for rule in ordering_rules.lines() {
+            let [page, rule] = parse::tuple(Day5::DAY, &s, rule, "|")?;
+            page_ordering.entry(page).or_default().insert(rule);
+        }
         let updates = updates
             .lines()
-            .map(|line| line.split(',').map(|page| page.parse().unwrap()).collect())
-            .collect();
-        SafetyManual::new(page_ordering, updates)
+            .map(|line| parse::list(Day5::DAY, &s, line, ",").collect())
+            .collect::<Result<_, _>>()?;
+        Ok(SafetyManual::new(page_ordering, updates))
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Puzzle = SafetyManual;
+
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(SafetyManual::try_from(input)?)
+    }
+
+    fn part_1(manual: &Self::Puzzle) -> Result<Answer> {
+        Ok(manual.calculate_valid_update_sums().into())
+    }
+
+    fn part_2(manual: &Self::Puzzle) -> Result<Answer> {
+        Ok(manual.calculate_incorrectly_update_sums().into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Rules cover every pair of pages, so they form one consistent order
+        let mut pages = (10..=99).collect::<Vec<u64>>();
+        rng.shuffle(&mut pages);
+        pages.truncate(size.clamp(5, pages.len()));
+        let mut rules = pages
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| pages[i + 1..].iter().map(move |b| format!("{}|{}", a, b)))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut rules);
+        let max_half = ((pages.len() as u64 - 1) / 2).min(11);
+        let updates = (0..size)
+            .map(|_| {
+                let mut update = pages.clone();
+                rng.shuffle(&mut update);
+                update.truncate(rng.range(1..=max_half) as usize * 2 + 1);
+                if rng.chance(0.5) {
+                    update.sort_by_key(|page| pages.iter().position(|p| p == page));
+                }
+                update
+                    .iter()
+                    .map(|page| page.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>();
+        format!("{}\n\n{}", rules.join("\n"), updates.join("\n"))
     }
 }
 
 // Elapsed time: 3987 us
 // Memory Used: 75.66113 kb
-pub fn calculate_correctly_ordered_updates(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let manual = SafetyManual::from(input);
-    Ok(manual.calculate_valid_update_sums())
+pub fn calculate_correctly_ordered_updates<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day5::part_1(&Day5::parse(input)?)
 }
 
 // Elapsed time: 337885 us
 // Memory Used: 75.66113 kb
-pub fn calculate_incorrectly_ordered_updates(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let manual = SafetyManual::from(input);
-    Ok(manual.calculate_incorrectly_update_sums())
+pub fn calculate_incorrectly_ordered_updates<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day5::part_2(&Day5::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    #[test]
+    fn calculate_correctly_ordered_updates_example() {
+        assert_eq!(calculate_correctly_ordered_updates(TEST).unwrap(), 143);
+    }
+
+    #[test]
+    fn calculate_incorrectly_ordered_updates_example() {
+        assert_eq!(calculate_incorrectly_ordered_updates(TEST).unwrap(), 123);
+    }
+
+    fn reference_manual(input: &str) -> (HashSet<(u64, u64)>, Vec<Vec<u64>>) {
+        let (rules, updates) = input.split_once("\n\n").unwrap();
+        let rules = rules
+            .lines()
+            .map(|l| {
+                let (a, b) = l.split_once('|').unwrap();
+                (a.parse().unwrap(), b.parse().unwrap())
+            })
+            .collect();
+        let updates = updates
+            .lines()
+            .map(|l| l.split(',').map(|n| n.parse().unwrap()).collect())
+            .collect();
+        (rules, updates)
+    }
+
+    fn reference_is_ordered(rules: &HashSet<(u64, u64)>, update: &[u64]) -> bool {
+        (0..update.len())
+            .all(|i| (i + 1..update.len()).all(|j| !rules.contains(&(update[j], update[i]))))
+    }
+
+    fn reference_part_1(input: &str) -> u64 {
+        let (rules, updates) = reference_manual(input);
+        updates
+            .iter()
+            .filter(|update| reference_is_ordered(&rules, update))
+            .map(|update| update[update.len() / 2])
+            .sum()
+    }
+
+    fn reference_part_2(input: &str) -> u64 {
+        let (rules, updates) = reference_manual(input);
+        updates
+            .into_iter()
+            .filter(|update| !reference_is_ordered(&rules, update))
+            .map(|mut update| {
+                update.sort_by(|a, b| {
+                    if rules.contains(&(*a, *b)) {
+                        std::cmp::Ordering::Less
+                    } else if rules.contains(&(*b, *a)) {
+                        std::cmp::Ordering::Greater
+                    } else {
+                        std::cmp::Ordering::Equal
+                    }
+                });
+                update[update.len() / 2]
+            })
+            .sum()
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day5, 8, reference_part_1, reference_part_2);
+    }
 }
@@ -1,88 +1,82 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    bitset::{PointDirectionSet, PointSet},
+    error::ParseError,
+    generate::Rng,
+    grid::Grid,
+    input::{get_input, Input},
+    point::{Cardinal, Point},
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_6.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_6.txt";
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Guard {
-    position: (i64, i64),
-    orientation: Orientation,
+    position: Point,
+    direction: Cardinal,
 }
 
-struct NorthPoleMap {
-    grid: Vec<Vec<char>>,
+pub struct NorthPoleMap {
+    grid: Grid<u8>,
     guard: Guard,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-enum Orientation {
-    North,
-    East,
-    South,
-    West,
-}
-
+/// Rows of each wall, keyed by column, and columns of each wall, keyed by row.
 struct Walls {
-    vertical: HashMap<i64, HashSet<i64>>,
-    horizontal: HashMap<i64, HashSet<i64>>,
+    columns: HashMap<i64, HashSet<i64>>,
+    rows: HashMap<i64, HashSet<i64>>,
 }
 
 impl Guard {
-    fn new(position: (i64, i64), orientation: Orientation) -> Self {
+    fn new(position: Point, direction: Cardinal) -> Self {
         Self {
             position,
-            orientation,
+            direction,
         }
     }
 
-    fn get_position(&self) -> (i64, i64) {
+    fn get_position(&self) -> Point {
         self.position
     }
 
-    fn get_next_position(&self) -> (i64, i64) {
-        match self.orientation {
-            Orientation::North => (self.position.0 - 1, self.position.1),
-            Orientation::East => (self.position.0, self.position.1 + 1),
-            Orientation::South => (self.position.0 + 1, self.position.1),
-            Orientation::West => (self.position.0, self.position.1 - 1),
-        }
+    fn get_next_position(&self) -> Point {
+        self.position + self.direction
     }
 
     fn r#move(&mut self) {
-        match self.orientation {
-            Orientation::North => self.position.0 -= 1,
-            Orientation::East => self.position.1 += 1,
-            Orientation::South => self.position.0 += 1,
-            Orientation::West => self.position.1 -= 1,
-        }
+        self.position += self.direction;
     }
 
-    fn set_position(&mut self, position: (i64, i64)) {
+    fn set_position(&mut self, position: Point) {
         self.position = position;
     }
 
     fn turn_right(&mut self) {
-        self.orientation = self.orientation.turn_right();
+        self.direction = self.direction.turn_right();
     }
 }
 
 impl NorthPoleMap {
-    fn new(grid: Vec<Vec<char>>, guard: Guard) -> Self {
+    fn new(grid: Grid<u8>, guard: Guard) -> Self {
         Self { grid, guard }
     }
 
-    fn check_is_cycle(guard: &Guard, walls: &Walls) -> bool {
-        let mut seen_positions = HashSet::new();
+    /// Whether the guard ends up walking in a loop. `seen` is only reused to save allocating it
+    /// for every call.
+    fn check_is_cycle(guard: &Guard, walls: &Walls, seen: &mut PointDirectionSet) -> bool {
+        seen.clear();
         let mut guard = guard.clone();
         loop {
             match walls.get_position_before_next_wall(&guard) {
                 Some(position) => {
                     guard.set_position(position);
                     guard.turn_right();
-                    if !seen_positions.insert(guard.clone()) {
+                    if !seen.insert(guard.position, guard.direction) {
                         return true;
                     }
                 }
@@ -93,15 +87,31 @@ impl NorthPoleMap {
         }
     }
 
-    fn get_guard_locations(&self) -> HashSet<(i64, i64)> {
-        let mut locations = HashSet::new();
+    fn guard_leaves(&self) -> bool {
+        let mut seen = PointDirectionSet::for_grid(&self.grid);
+        let mut guard = self.guard.clone();
+        while seen.insert(guard.position, guard.direction) {
+            let next_position = guard.get_next_position();
+            if !self.grid.contains(next_position) {
+                return true;
+            } else if self.grid[next_position] == b'#' {
+                guard.turn_right();
+            } else {
+                guard.r#move();
+            }
+        }
+        false
+    }
+
+    fn get_guard_locations(&self) -> PointSet {
+        let mut locations = PointSet::for_grid(&self.grid);
         let mut guard = self.guard.clone();
         loop {
             locations.insert(guard.get_position());
             let next_position = guard.get_next_position();
-            if !self.is_in_map(next_position) {
+            if !self.grid.contains(next_position) {
                 break;
-            } else if self.grid[next_position.0 as usize][next_position.1 as usize] == '#' {
+            } else if self.grid[next_position] == b'#' {
                 guard.turn_right();
             } else {
                 guard.r#move();
@@ -110,22 +120,23 @@ impl NorthPoleMap {
         locations
     }
 
-    fn get_wall_cycle_locations(&self) -> HashSet<(i64, i64)> {
+    fn get_wall_cycle_locations(&self) -> PointSet {
         let mut walls = Walls::from(self);
-        let mut seen_positions = HashSet::new();
-        let mut cycle_wall_locations = HashSet::new();
+        let mut seen_positions = PointSet::for_grid(&self.grid);
+        let mut cycle_wall_locations = PointSet::for_grid(&self.grid);
+        let mut seen_states = PointDirectionSet::for_grid(&self.grid);
         let mut guard = self.guard.clone();
         loop {
             seen_positions.insert(guard.get_position());
             let next_position = guard.get_next_position();
-            if !self.is_in_map(next_position) {
+            if !self.grid.contains(next_position) {
                 break;
-            } else if self.grid[next_position.0 as usize][next_position.1 as usize] == '#' {
+            } else if self.grid[next_position] == b'#' {
                 guard.turn_right();
             } else {
                 walls.insert(next_position);
-                if !seen_positions.contains(&next_position)
-                    && NorthPoleMap::check_is_cycle(&guard, &walls)
+                if !seen_positions.contains(next_position)
+                    && NorthPoleMap::check_is_cycle(&guard, &walls, &mut seen_states)
                 {
                     cycle_wall_locations.insert(next_position);
                 }
@@ -133,174 +144,214 @@ impl NorthPoleMap {
                 guard.r#move();
             }
         }
-        cycle_wall_locations.remove(&self.guard.get_position());
+        cycle_wall_locations.remove(self.guard.get_position());
         cycle_wall_locations
     }
-
-    fn is_in_map(&self, position: (i64, i64)) -> bool {
-        (0..self.grid.len()).contains(&(position.0 as usize))
-            && (0..self.grid[position.0 as usize].len()).contains(&(position.1 as usize))
-    }
 }
 
-impl From<String> for NorthPoleMap {
-    fn from(input: String) -> Self {
-        let obstacles = input.lines().map(|line| line.chars().collect()).collect();
-        let guard = input
-            .lines()
-            .enumerate()
-            .find_map(|(i, line)| {
-                line.chars().enumerate().find_map(|(j, c)| match c {
-                    '^' => Some(Guard::new((i as i64, j as i64), Orientation::North)),
-                    '>' => Some(Guard::new((i as i64, j as i64), Orientation::East)),
-                    'v' => Some(Guard::new((i as i64, j as i64), Orientation::South)),
-                    '<' => Some(Guard::new((i as i64, j as i64), Orientation::West)),
-                    _ => None,
-                })
-            })
-            .unwrap();
-        Self::new(obstacles, guard)
-    }
-}
+impl TryFrom<String> for NorthPoleMap {
+    type Error = ParseError;
 
-impl Orientation {
-    fn turn_right(&self) -> Self {
-        match self {
-            Self::North => Self::East,
-            Self::East => Self::South,
-            Self::South => Self::West,
-            Self::West => Self::North,
-        }
+    fn try_from(input: String) -> Result<Self, ParseError> {
+        let grid = Grid::parse_bytes(Day6::DAY, &input, |b| b)?;
+        let guard = grid
+            .iter()
+            .find_map(|(position, c)| match c {
+                b'^' => Some(Guard::new(position, Cardinal::North)),
+                b'>' => Some(Guard::new(position, Cardinal::East)),
+                b'v' => Some(Guard::new(position, Cardinal::South)),
+                b'<' => Some(Guard::new(position, Cardinal::West)),
+                _ => None,
+            })
+            .ok_or_else(|| ParseError::new(Day6::DAY, 1, 1, "", "no guard found on the map"))?;
+        Ok(Self::new(grid, guard))
     }
 }
 
 impl Walls {
     fn new() -> Self {
         Self {
-            vertical: HashMap::new(),
-            horizontal: HashMap::new(),
+            columns: HashMap::new(),
+            rows: HashMap::new(),
         }
     }
 
-    fn get_position_before_next_wall(&self, guard: &Guard) -> Option<(i64, i64)> {
-        let position = guard.get_position();
-        match guard.orientation {
-            Orientation::North => self
-                .vertical
-                .get(&position.1)
-                .and_then(|walls| {
-                    walls
-                        .iter()
-                        .filter_map(|wall| {
-                            if *wall < position.0 {
-                                Some((position.0 - wall, *wall))
-                            } else {
-                                None
-                            }
-                        })
-                        .min()
-                })
-                .map(|wall| (wall.1 + 1, position.1)),
-            Orientation::East => self
-                .horizontal
-                .get(&position.0)
-                .and_then(|walls| {
-                    walls
-                        .iter()
-                        .filter_map(|wall| {
-                            if *wall > position.1 {
-                                Some((*wall - position.1, *wall))
-                            } else {
-                                None
-                            }
-                        })
-                        .min()
-                })
-                .map(|wall| (position.0, wall.1 - 1)),
-            Orientation::South => self
-                .vertical
-                .get(&position.1)
-                .and_then(|walls| {
-                    walls
-                        .iter()
-                        .filter_map(|wall| {
-                            if *wall > position.0 {
-                                Some((wall - position.0, *wall))
-                            } else {
-                                None
-                            }
-                        })
-                        .min()
-                })
-                .map(|wall| (wall.1 - 1, position.1)),
-            Orientation::West => self
-                .horizontal
-                .get(&position.0)
-                .and_then(|walls| {
-                    walls
-                        .iter()
-                        .filter_map(|wall| {
-                            if *wall < position.1 {
-                                Some((position.1 - wall, *wall))
-                            } else {
-                                None
-                            }
-                        })
-                        .min()
-                })
-                .map(|wall| (position.0, wall.1 + 1)),
+    fn get_position_before_next_wall(&self, guard: &Guard) -> Option<Point> {
+        let Point { x, y } = guard.get_position();
+        // Closest wall strictly ahead of `from` when moving by `step`, and the cell just before it
+        let before_wall = |walls: Option<&HashSet<i64>>, from: i64, step: i64| {
+            walls?
+                .iter()
+                .filter(|&&wall| (wall - from) * step > 0)
+                .min_by_key(|&&wall| (wall - from) * step)
+                .map(|wall| wall - step)
+        };
+        match guard.direction {
+            Cardinal::North => before_wall(self.columns.get(&x), y, -1).map(|y| Point::new(x, y)),
+            Cardinal::South => before_wall(self.columns.get(&x), y, 1).map(|y| Point::new(x, y)),
+            Cardinal::East => before_wall(self.rows.get(&y), x, 1).map(|x| Point::new(x, y)),
+            Cardinal::West => before_wall(self.rows.get(&y), x, -1).map(|x| Point::new(x, y)),
         }
     }
 
-    fn insert(&mut self, wall: (i64, i64)) {
-        self.vertical
-            .entry(wall.1)
-            .or_insert_with(HashSet::new)
-            .insert(wall.0);
-        self.horizontal
-            .entry(wall.0)
-            .or_insert_with(HashSet::new)
-            .insert(wall.1);
+    fn insert(&mut self, wall: Point) {
+        self.columns.entry(wall.x).or_default().insert(wall.y);
+        self.rows.entry(wall.y).or_default().insert(wall.x);
     }
 
-    fn remove(&mut self, wall: (i64, i64)) {
-        self.vertical
-            .get_mut(&wall.1)
-            .map(|walls| walls.remove(&wall.0));
-        self.horizontal
-            .get_mut(&wall.0)
-            .map(|walls| walls.remove(&wall.1));
+    fn remove(&mut self, wall: Point) {
+        self.columns
+            .get_mut(&wall.x)
+            .map(|walls| walls.remove(&wall.y));
+        self.rows
+            .get_mut(&wall.y)
+            .map(|walls| walls.remove(&wall.x));
     }
 }
 
 impl From<&NorthPoleMap> for Walls {
     fn from(map: &NorthPoleMap) -> Self {
         let mut walls = Self::new();
-        map.grid.iter().enumerate().for_each(|(i, row)| {
-            row.iter().enumerate().for_each(|(j, c)| {
-                if *c == '#' {
-                    walls.insert((i as i64, j as i64));
-                }
-            })
-        });
+        map.grid
+            .positions(|&c| c == b'#')
+            .for_each(|wall| walls.insert(wall));
         walls
     }
 }
 
-// Elapsed time: 3943 us
-// Memory Used: 282.9297 kb
-pub fn find_distinct_guard_positions(input: &str) -> Result<usize> {
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Puzzle = NorthPoleMap;
+
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(NorthPoleMap::try_from(input)?)
+    }
+
+    fn part_1(map: &Self::Puzzle) -> Result<Answer> {
+        Ok(map.get_guard_locations().len().into())
+    }
+
+    fn part_2(map: &Self::Puzzle) -> Result<Answer> {
+        Ok(map.get_wall_cycle_locations().len().into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Both parts expect the guard to walk off the map, so retry maps that trap the guard
+        loop {
+            let mut cells = (0..size * size)
+                .map(|_| if rng.chance(0.05) { b'#' } else { b'.' })
+                .collect::<Vec<_>>();
+            let guard = rng.range(0..=cells.len() as u64 - 1) as usize;
+            cells[guard] = b'^';
+            let grid = Grid::new(size, size, cells);
+            let guard = Point::new((guard % size) as i64, (guard / size) as i64);
+            let map = NorthPoleMap::new(grid, Guard::new(guard, Cardinal::North));
+            if map.guard_leaves() {
+                return map.grid.map(|&c| char::from(c)).to_string();
+            }
+        }
+    }
+}
+
+// Elapsed time: 3943 us (debug build)
+// Memory Used: 282.9297 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 49.4 us, peak 66.51953 kb; solve 537.1 us, peak 204.03125 kb
+// Release bench, byte grid: parse 10.3 us, peak 16.629883 kb; solve 318.9 us, peak 204.03125 kb
+// Release bench, bitsets: parse 9.0 us, peak 16.629883 kb; solve 25.6 us, peak 2.0703125 kb
+pub fn find_distinct_guard_positions<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
     let input = get_input(input)?;
-    let map = NorthPoleMap::from(input);
-    let locations = map.get_guard_locations();
-    Ok(locations.len())
+    Day6::part_1(&Day6::parse(input)?)
 }
 
-// Elapsed time: 236772 us
-// Memory Used: 375.8711 kb
-pub fn find_wall_cycle_locations(input: &str) -> Result<usize> {
+// Elapsed time: 236772 us (debug build)
+// Memory Used: 375.8711 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 58.8 us, peak 66.51953 kb; solve 38874.1 us, peak 296.97266 kb
+// Release bench, byte grid: parse 17.2 us, peak 16.629883 kb; solve 33261.8 us, peak 296.97266 kb
+// Release bench, bitsets: parse 11.8 us, peak 16.629883 kb; solve 11786.7 us, peak 71.48047 kb
+pub fn find_wall_cycle_locations<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
     let input = get_input(input)?;
-    let map = NorthPoleMap::from(input);
-    let wall_cycle_locations = map.get_wall_cycle_locations();
-    Ok(wall_cycle_locations.len())
+    Day6::part_2(&Day6::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    #[test]
+    fn find_distinct_guard_positions_example() {
+        assert_eq!(find_distinct_guard_positions(TEST).unwrap(), 41);
+    }
+
+    #[test]
+    fn find_wall_cycle_locations_example() {
+        assert_eq!(find_wall_cycle_locations(TEST).unwrap(), 6);
+    }
+
+    /// Walks the guard one step at a time, returning the visited cells, or `None` if the guard
+    /// ends up walking in a loop.
+    fn reference_walk(grid: &[Vec<char>]) -> Option<HashSet<(i64, i64)>> {
+        let (mut i, mut j) = (0..grid.len())
+            .flat_map(|i| (0..grid[i].len()).map(move |j| (i, j)))
+            .find(|&(i, j)| grid[i][j] == '^')
+            .map(|(i, j)| (i as i64, j as i64))
+            .unwrap();
+        let (mut di, mut dj) = (-1, 0);
+        let mut states = HashSet::new();
+        while states.insert((i, j, di, dj)) {
+            let (ni, nj) = (i + di, j + dj);
+            let Some(&cell) = grid
+                .get(ni as usize)
+                .and_then(|row| row.get(nj as usize))
+                .filter(|_| ni >= 0 && nj >= 0)
+            else {
+                return Some(states.iter().map(|&(i, j, _, _)| (i, j)).collect());
+            };
+            if cell == '#' {
+                (di, dj) = (dj, -di);
+            } else {
+                (i, j) = (ni, nj);
+            }
+        }
+        None
+    }
+
+    fn reference_part_1(input: &str) -> usize {
+        let grid = input
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect::<Vec<Vec<_>>>();
+        reference_walk(&grid).unwrap().len()
+    }
+
+    fn reference_part_2(input: &str) -> usize {
+        let mut grid = input
+            .lines()
+            .map(|l| l.chars().collect())
+            .collect::<Vec<Vec<_>>>();
+        let mut loops = 0;
+        for i in 0..grid.len() {
+            for j in 0..grid[i].len() {
+                if grid[i][j] == '.' {
+                    grid[i][j] = '#';
+                    if reference_walk(&grid).is_none() {
+                        loops += 1;
+                    }
+                    grid[i][j] = '.';
+                }
+            }
+        }
+        loops
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day6, 10, reference_part_1, reference_part_2);
+    }
 }
@@ -1,11 +1,25 @@
-use crate::get_input;
+use std::str::FromStr;
+
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    input::{get_input, Input},
+    parse,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_7.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_7.txt";
+
+type Operator = Box<dyn Fn(u64, u64) -> Option<u64>>;
+
+// Keeps generated results near the official ones, so summing many of them cannot overflow
+const MAX_GENERATED_RESULT: u64 = 100_000_000_000_000;
 
 #[derive(Debug)]
-struct Equation {
+pub struct Equation {
     result: u64,
     numbers: Vec<u64>,
 }
@@ -15,11 +29,9 @@ impl Equation {
         Self { result, numbers }
     }
 
-    fn is_solvable(&self, functions: Vec<Box<dyn Fn(u64, u64) -> Option<u64>>>) -> bool {
-        let _ = functions;
+    fn is_solvable(&self, functions: &[Operator]) -> bool {
         let mut results = vec![*self.numbers.first().unwrap()];
-        let mut numbers = self.numbers.iter().skip(1);
-        while let Some(n) = numbers.next() {
+        for n in self.numbers.iter().skip(1) {
             results = functions
                 .iter()
                 .flat_map(|f| {
@@ -33,12 +45,16 @@ impl Equation {
     }
 }
 
-impl From<&str> for Equation {
-    fn from(s: &str) -> Self {
-        let (result, numbers) = s.split_once(": ").unwrap();
-        let result = result.parse().unwrap();
-        let numbers = numbers.split(" ").map(|n| n.parse().unwrap()).collect();
-        Self::new(result, numbers)
+impl FromStr for Equation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let (result, numbers) = s.split_once(": ").ok_or_else(|| {
+            ParseError::at(Day7::DAY, s, s, "expected an equation like 190: 10 19")
+        })?;
+        let result = parse::number(Day7::DAY, s, result)?;
+        let numbers = parse::list(Day7::DAY, s, numbers, " ").collect::<Result<_, _>>()?;
+        Ok(Self::new(result, numbers))
     }
 }
 
@@ -57,36 +73,173 @@ fn concat(a: u64, b: u64) -> Option<u64> {
         .and_then(|p| p.checked_add(b))
 }
 
-// Elapsed time: 18821 us
-// Memory Used: 59.770508 kb
-pub fn find_valid_equations_without_concatenation(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    Ok(input
-        .lines()
-        .filter_map(|l| {
-            let equation = Equation::from(l);
-            if equation.is_solvable(vec![Box::new(add), Box::new(mul)]) {
+fn sum_solvable(equations: &[Equation], functions: &[Operator]) -> u64 {
+    equations
+        .iter()
+        .filter_map(|equation| {
+            if equation.is_solvable(functions) {
                 Some(equation.result)
             } else {
                 None
             }
         })
-        .sum())
+        .sum()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Puzzle = Vec<Equation>;
+
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Bridge Repair";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| l.parse().map_err(|e: ParseError| e.on_line(i + 1)))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part_1(equations: &Self::Puzzle) -> Result<Answer> {
+        Ok(sum_solvable(equations, &[Box::new(add), Box::new(mul)]).into())
+    }
+
+    fn part_2(equations: &Self::Puzzle) -> Result<Answer> {
+        Ok(sum_solvable(equations, &[Box::new(add), Box::new(mul), Box::new(concat)]).into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let operators: [Operator; 3] = [Box::new(add), Box::new(mul), Box::new(concat)];
+        (0..size)
+            .map(|_| {
+                let numbers = (0..rng.range(2..=12))
+                    .map(|_| {
+                        if rng.chance(0.7) {
+                            rng.range(1..=99)
+                        } else {
+                            rng.range(100..=999)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let mut result = numbers[0];
+                for &n in &numbers[1..] {
+                    let operator = rng.pick(&operators);
+                    result = operator(result, n)
+                        .filter(|&r| r < MAX_GENERATED_RESULT)
+                        .unwrap_or(result + n);
+                }
+                // Roughly a third of the equations cannot be solved
+                if rng.chance(0.3) {
+                    result += 1;
+                }
+                let numbers = numbers.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+                format!("{}: {}", result, numbers.join(" "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+// Elapsed time: 18821 us
+// Memory Used: 59.770508 kb
+pub fn find_valid_equations_without_concatenation<'a>(
+    input: impl Into<Input<'a>>,
+) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day7::part_1(&Day7::parse(input)?)
 }
 
 // Elapsed time: 502076 us
 // Memory Used: 3611.7861 kb
-pub fn find_valid_equations_with_concatenation(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    Ok(input
-        .lines()
-        .filter_map(|l| {
-            let equation = Equation::from(l);
-            if equation.is_solvable(vec![Box::new(add), Box::new(mul), Box::new(concat)]) {
-                Some(equation.result)
-            } else {
-                None
+pub fn find_valid_equations_with_concatenation<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day7::part_2(&Day7::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    #[test]
+    fn find_valid_equations_without_concatenation_example() {
+        assert_eq!(
+            find_valid_equations_without_concatenation(TEST).unwrap(),
+            3749
+        );
+    }
+
+    #[test]
+    fn find_valid_equations_with_concatenation_example() {
+        assert_eq!(
+            find_valid_equations_with_concatenation(TEST).unwrap(),
+            11387
+        );
+    }
+
+    #[test]
+    fn parse_reports_line_and_column() {
+        let error = Day7::parse(String::from("190: 10 19\n3267: 81 x 27"))
+            .err()
+            .and_then(|e| e.downcast::<ParseError>().ok())
+            .unwrap();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 10, "x")
+        );
+    }
+
+    /// Tries every combination of operators, evaluated left to right in `u128`.
+    fn reference_sum(input: &str, with_concatenation: bool) -> u128 {
+        fn results(numbers: &[u128], with_concatenation: bool) -> Vec<u128> {
+            let Some((&last, rest)) = numbers.split_last() else {
+                return Vec::new();
+            };
+            if rest.is_empty() {
+                return vec![last];
             }
-        })
-        .sum())
+            let mut all = Vec::new();
+            for r in results(rest, with_concatenation) {
+                all.push(r + last);
+                all.push(r * last);
+                if with_concatenation {
+                    all.push(format!("{}{}", r, last).parse().unwrap_or(u128::MAX));
+                }
+            }
+            all
+        }
+        input
+            .lines()
+            .map(|l| {
+                let (result, numbers) = l.split_once(": ").unwrap();
+                let result = result.parse::<u128>().unwrap();
+                let numbers = numbers
+                    .split(' ')
+                    .map(|n| n.parse().unwrap())
+                    .collect::<Vec<_>>();
+                if results(&numbers, with_concatenation).contains(&result) {
+                    result
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    fn reference_part_1(input: &str) -> u128 {
+        reference_sum(input, false)
+    }
+
+    fn reference_part_2(input: &str) -> u128 {
+        reference_sum(input, true)
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day7, 10, reference_part_1, reference_part_2);
+    }
 }
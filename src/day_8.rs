@@ -1,51 +1,66 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    grid::Grid,
+    input::{get_input, Input},
+    point::Point,
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_8.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_8.txt";
 
-struct Antennas {
-    grid: Vec<Vec<char>>,
-    antennas: HashMap<char, Vec<(i64, i64)>>,
+type AntennaPairs = HashMap<u8, Vec<(Point, Point)>>;
+
+pub struct Antennas {
+    grid: Grid<u8>,
+    antennas: HashMap<u8, Vec<Point>>,
 }
 
 impl Antennas {
-    fn new(grid: Vec<Vec<char>>) -> Self {
-        let antennas = grid
-            .iter()
-            .enumerate()
-            .fold(HashMap::new(), |mut acc, (y, row)| {
-                row.iter().enumerate().for_each(|(x, frequency)| {
-                    if frequency.is_alphanumeric() {
-                        acc.entry(*frequency)
-                            .or_insert_with(Vec::new)
-                            .push((x as i64, y as i64));
-                    }
-                });
+    fn new(grid: Grid<u8>) -> Self {
+        let antennas = grid.iter().fold(
+            HashMap::new(),
+            |mut acc: HashMap<u8, Vec<_>>, (position, frequency)| {
+                if frequency.is_ascii_alphanumeric() {
+                    acc.entry(*frequency).or_default().push(position);
+                }
                 acc
-            });
+            },
+        );
         Self { grid, antennas }
     }
 
-    fn find_antinodes(&self, jumps: Option<u64>, starting_jump: u64) -> HashSet<(i64, i64)> {
+    fn find_antinodes(&self, jumps: Option<u64>, starting_jump: u64) -> HashSet<Point> {
         let antenna_pairs = self.get_antenna_pairs();
 
         antenna_pairs
-            .iter()
-            .flat_map(|(_frequency, pairs)| {
+            .values()
+            .flat_map(|pairs| {
                 pairs.iter().flat_map(|(a, b)| {
                     let mut antinodes = Vec::new();
                     let mut curr_jumps = starting_jump;
-                    let dx = b.0 - a.0;
-                    let dy = b.1 - a.1;
-                    while jumps.map_or(true, |j| curr_jumps <= j) {
-                        let antinode_1 =
-                            (b.0 + dx * curr_jumps as i64, b.1 + dy * curr_jumps as i64);
-                        let antinode_2 =
-                            (a.0 - dx * curr_jumps as i64, a.1 - dy * curr_jumps as i64);
-                        match (self.is_in_grid(antinode_1), self.is_in_grid(antinode_2)) {
+                    // Without a jump limit every grid point in line with the antennas counts,
+                    // including those between them, so walk from `a` in the smallest steps
+                    let (origin, step) = match jumps {
+                        Some(_) => (*b, *b - *a),
+                        None => {
+                            let step = *b - *a;
+                            let divisor = gcd(step.x, step.y);
+                            (*a, Point::new(step.x / divisor, step.y / divisor))
+                        }
+                    };
+                    while jumps.is_none_or(|j| curr_jumps <= j) {
+                        let antinode_1 = origin + step * curr_jumps as i64;
+                        let antinode_2 = *a - step * curr_jumps as i64;
+                        match (
+                            self.grid.contains(antinode_1),
+                            self.grid.contains(antinode_2),
+                        ) {
                             (true, true) => {
                                 antinodes.push(antinode_1);
                                 antinodes.push(antinode_2);
@@ -62,7 +77,7 @@ impl Antennas {
             .collect()
     }
 
-    fn get_antenna_pairs(&self) -> HashMap<char, Vec<((i64, i64), (i64, i64))>> {
+    fn get_antenna_pairs(&self) -> AntennaPairs {
         self.antennas
             .iter()
             .map(|(frequency, antennas)| {
@@ -77,32 +92,155 @@ impl Antennas {
             })
             .collect::<HashMap<_, _>>()
     }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl TryFrom<String> for Antennas {
+    type Error = ParseError;
 
-    fn is_in_grid(&self, node: (i64, i64)) -> bool {
-        (0..self.grid.len() as i64).contains(&node.1)
-            && (0..self.grid[0].len() as i64).contains(&node.0)
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        let grid = Grid::parse_bytes(Day8::DAY, &s, |b| b)?;
+        Ok(Self::new(grid))
     }
 }
 
-impl From<String> for Antennas {
-    fn from(s: String) -> Self {
-        let grid = s.lines().map(|l| l.chars().collect()).collect();
-        Self::new(grid)
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Puzzle = Antennas;
+
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Resonant Collinearity";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(Antennas::try_from(input)?)
+    }
+
+    fn part_1(antennas: &Self::Puzzle) -> Result<Answer> {
+        Ok(antennas.find_antinodes(Some(1), 1).len().into())
+    }
+
+    fn part_2(antennas: &Self::Puzzle) -> Result<Answer> {
+        Ok(antennas.find_antinodes(None, 0).len().into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const FREQUENCIES: &[u8] =
+            b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        let cells = (0..size * size)
+            .map(|_| {
+                if rng.chance(0.04) {
+                    *rng.pick(FREQUENCIES) as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        Grid::new(size, size, cells).to_string()
     }
 }
 
-// Elapsed time: 620 us
-// Memory Used: 53.398438 kb
-pub fn find_all_antinodes(path: &str) -> Result<usize> {
-    let input = get_input(path)?;
-    let antennas = Antennas::from(input);
-    Ok(antennas.find_antinodes(Some(1), 1).len())
+// Elapsed time: 620 us (debug build)
+// Memory Used: 53.398438 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 31.7 us, peak 14.832031 kb; solve 74.8 us, peak 32.671875 kb
+// Release bench, byte grid: parse 27.4 us, peak 7.364258 kb; solve 74.4 us, peak 32.671875 kb
+pub fn find_all_antinodes<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day8::part_1(&Day8::parse(input)?)
 }
 
-// Elapsed time: 1462 us
-// Memory Used: 66.21094 kb
-pub fn find_all_extended_antinodes(path: &str) -> Result<usize> {
-    let input = get_input(path)?;
-    let antennas = Antennas::from(input);
-    Ok(antennas.find_antinodes(None, 0).len())
+// Elapsed time: 1462 us (debug build)
+// Memory Used: 66.21094 kb (debug build, whole process peak)
+// Release bench, Grid<char>: parse 33.7 us, peak 14.832031 kb; solve 257.4 us, peak 45.609375 kb
+// Release bench, byte grid: parse 28.5 us, peak 7.364258 kb; solve 258.5 us, peak 45.609375 kb
+pub fn find_all_extended_antinodes<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day8::part_2(&Day8::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    const TEST_2: &str = "inputs/examples/day_8_2.txt";
+    const TEST_3: &str = "inputs/examples/day_8_3.txt";
+    const TEST_4: &str = "inputs/examples/day_8_4.txt";
+    const TEST_5: &str = "inputs/examples/day_8_5.txt";
+    const TEST_6: &str = "inputs/examples/day_8_6.txt";
+
+    #[test]
+    fn find_all_antinodes_examples() {
+        assert_eq!(find_all_antinodes(TEST).unwrap(), 14);
+        assert_eq!(find_all_antinodes(TEST_2).unwrap(), 2);
+        assert_eq!(find_all_antinodes(TEST_3).unwrap(), 4);
+        assert_eq!(find_all_antinodes(TEST_4).unwrap(), 4);
+    }
+
+    #[test]
+    fn find_all_extended_antinodes_examples() {
+        assert_eq!(find_all_extended_antinodes(TEST).unwrap(), 34);
+        assert_eq!(find_all_extended_antinodes(TEST_5).unwrap(), 9);
+    }
+
+    #[test]
+    fn extended_antinodes_between_antennas_with_a_common_factor() {
+        // The antennas are two steps of (1, 1) apart, so the cells between and beyond them count
+        assert_eq!(find_all_antinodes(TEST_6).unwrap(), 1);
+        assert_eq!(find_all_extended_antinodes(TEST_6).unwrap(), 5);
+    }
+
+    fn reference_antennas(input: &str) -> (i64, i64, Vec<(char, Point)>) {
+        let lines = input.lines().collect::<Vec<_>>();
+        let antennas = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(y, l)| {
+                l.chars()
+                    .enumerate()
+                    .filter(|(_, c)| c.is_alphanumeric())
+                    .map(move |(x, c)| (c, Point::new(x as i64, y as i64)))
+            })
+            .collect();
+        (lines[0].len() as i64, lines.len() as i64, antennas)
+    }
+
+    /// Counts the cells where `is_antinode` holds for a pair of antennas of the same frequency.
+    fn reference_count(input: &str, is_antinode: fn(Point, Point, Point) -> bool) -> usize {
+        let (width, height, antennas) = reference_antennas(input);
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .filter(|&p| {
+                antennas.iter().any(|&(f, a)| {
+                    antennas
+                        .iter()
+                        .any(|&(g, b)| f == g && a != b && is_antinode(p, a, b))
+                })
+            })
+            .count()
+    }
+
+    fn reference_part_1(input: &str) -> usize {
+        reference_count(input, |p, a, b| p == b * 2 - a)
+    }
+
+    fn reference_part_2(input: &str) -> usize {
+        reference_count(input, |p, a, b| {
+            (b.x - a.x) * (p.y - a.y) == (b.y - a.y) * (p.x - a.x)
+        })
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day8, 10, reference_part_1, reference_part_2);
+    }
 }
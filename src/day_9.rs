@@ -1,10 +1,16 @@
-use std::{fmt::Debug, iter};
+use std::fmt::Debug;
 
-use crate::get_input;
+use crate::{
+    answer::Answer,
+    error::ParseError,
+    generate::Rng,
+    input::{get_input, Input},
+    solution::Solution,
+};
 use anyhow::Result;
 
 pub const INPUT: &str = "inputs/day_9.txt";
-pub const TEST: &str = "inputs/test.txt";
+pub const TEST: &str = "inputs/examples/day_9.txt";
 
 #[derive(Clone)]
 enum Data {
@@ -12,6 +18,10 @@ enum Data {
     Empty(u64),
 }
 
+pub struct DiskMap {
+    sizes: Vec<u64>,
+}
+
 struct FileSystem {
     data: Vec<Option<u64>>,
 }
@@ -51,13 +61,15 @@ impl Data {
 }
 
 impl FileSystem {
-    pub fn new(data: Vec<Option<u64>>) -> Self {
-        Self { data }
-    }
-
     fn compress_data(&mut self) {
-        let mut i = self.data.iter().position(|f| f.is_none()).unwrap();
-        let mut j = self.data.len() - self.data.iter().rev().position(|f| f.is_some()).unwrap() - 1;
+        // Without free space or files there is nothing to move
+        let (Some(mut i), Some(last)) = (
+            self.data.iter().position(|f| f.is_none()),
+            self.data.iter().rev().position(|f| f.is_some()),
+        ) else {
+            return;
+        };
+        let mut j = self.data.len() - last - 1;
         while i < j {
             if self.data[j].is_none() {
                 j -= 1;
@@ -80,18 +92,32 @@ impl FileSystem {
     }
 }
 
-impl From<String> for FileSystem {
-    fn from(s: String) -> Self {
-        let data = s
-            .chars()
+impl TryFrom<String> for DiskMap {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        let sizes = s
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10).map(|d| d as u64).ok_or_else(|| {
+                    ParseError::at(Day9::DAY, &s, &s[i..i + c.len_utf8()], "expected a digit")
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { sizes })
+    }
+}
+
+impl From<&DiskMap> for FileSystem {
+    fn from(disk_map: &DiskMap) -> Self {
+        let data = disk_map
+            .sizes
+            .iter()
             .enumerate()
-            .flat_map(|(i, c)| {
-                let size = c.to_digit(10).unwrap();
-                match i % 2 {
-                    0 => vec![Some(i as u64 / 2); size as usize],
-                    1 => vec![None; size as usize],
-                    _ => unreachable!(),
-                }
+            .flat_map(|(i, &size)| match i % 2 {
+                0 => vec![Some(i as u64 / 2); size as usize],
+                1 => vec![None; size as usize],
+                _ => unreachable!(),
             })
             .collect();
         Self { data }
@@ -108,13 +134,14 @@ impl Debug for FileSystem {
 }
 
 impl FileSystemImproved {
-    pub fn new(data: Vec<Data>) -> Self {
-        Self { data }
-    }
-
     fn compress_data(&mut self) {
-        let mut i = self.data.iter().position(|f| f.is_empty()).unwrap();
-        let mut j = self.data.len() - self.data.iter().rev().position(|f| f.is_file()).unwrap() - 1;
+        let (Some(mut i), Some(last)) = (
+            self.data.iter().position(|f| f.is_empty()),
+            self.data.iter().rev().position(|f| f.is_file()),
+        ) else {
+            return;
+        };
+        let mut j = self.data.len() - last - 1;
         while i < j {
             if self.data[j].is_empty() {
                 j -= 1;
@@ -163,27 +190,22 @@ impl FileSystemImproved {
             .fold((0, 0), |(total, ind), f| {
                 let size = f.get_size();
                 let fid = f.fid_or(0);
-                (
-                    total + (ind..ind + size).map(|i| i).sum::<u64>() * fid,
-                    ind + size,
-                )
+                (total + (ind..ind + size).sum::<u64>() * fid, ind + size)
             })
             .0
     }
 }
 
-impl From<String> for FileSystemImproved {
-    fn from(s: String) -> Self {
-        let data = s
-            .chars()
+impl From<&DiskMap> for FileSystemImproved {
+    fn from(disk_map: &DiskMap) -> Self {
+        let data = disk_map
+            .sizes
+            .iter()
             .enumerate()
-            .map(|(i, c)| {
-                let size = c.to_digit(10).unwrap();
-                match i % 2 {
-                    0 => Data::File(i as u64 / 2, size as u64),
-                    1 => Data::Empty(size as u64),
-                    _ => unreachable!(),
-                }
+            .map(|(i, &size)| match i % 2 {
+                0 => Data::File(i as u64 / 2, size),
+                1 => Data::Empty(size),
+                _ => unreachable!(),
             })
             .collect();
         Self { data }
@@ -193,39 +215,144 @@ impl From<String> for FileSystemImproved {
 impl Debug for FileSystemImproved {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         self.data.iter().for_each(|v| match v {
-            Data::File(fid, size) => write!(
-                f,
-                "{}",
-                iter::repeat(fid.to_string())
-                    .take(*size as usize)
-                    .collect::<String>()
-            )
-            .unwrap(),
-            Data::Empty(size) => write!(
-                f,
-                "{}",
-                iter::repeat(".").take(*size as usize).collect::<String>()
-            )
-            .unwrap(),
+            Data::File(fid, size) => {
+                write!(f, "{}", fid.to_string().repeat(*size as usize)).unwrap()
+            }
+            Data::Empty(size) => write!(f, "{}", ".".repeat(*size as usize)).unwrap(),
         });
         Ok(())
     }
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Puzzle = DiskMap;
+
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Disk Fragmenter";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(DiskMap::try_from(input)?)
+    }
+
+    fn part_1(disk_map: &Self::Puzzle) -> Result<Answer> {
+        let mut filesystem = FileSystem::from(disk_map);
+        filesystem.compress_data();
+        Ok(filesystem.calculate_checksum().into())
+    }
+
+    fn part_2(disk_map: &Self::Puzzle) -> Result<Answer> {
+        let mut filesystem = FileSystemImproved::from(disk_map);
+        filesystem.compress_data();
+        Ok(filesystem.calculate_checksum().into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Files take one to nine blocks, the free space between them zero to nine
+        (0..size.max(1) * 2 - 1)
+            .map(|i| {
+                let blocks = if i % 2 == 0 {
+                    rng.range(1..=9)
+                } else {
+                    rng.range(0..=9)
+                };
+                char::from_digit(blocks as u32, 10).unwrap()
+            })
+            .collect()
+    }
+}
+
 // Elapsed time: 24319 us
 // Memory Used: 2711.5068 kb
-pub fn compress_filesystem(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let mut filesystem = FileSystem::from(input);
-    filesystem.compress_data();
-    Ok(filesystem.calculate_checksum())
+pub fn compress_filesystem<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day9::part_1(&Day9::parse(input)?)
 }
 
 // Elapsed time: 8176778 us
 // Memory Used: 1410.3672 kb
-pub fn compress_filesystem_improved(path: &str) -> Result<u64> {
-    let input = get_input(path)?;
-    let mut filesystem = FileSystemImproved::from(input);
-    filesystem.compress_data();
-    Ok(filesystem.calculate_checksum())
+pub fn compress_filesystem_improved<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    let input = get_input(input)?;
+    Day9::part_2(&Day9::parse(input)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::differential;
+
+    #[test]
+    fn compress_filesystem_example() {
+        assert_eq!(compress_filesystem(TEST).unwrap(), 1928);
+    }
+
+    #[test]
+    fn compress_filesystem_improved_example() {
+        assert_eq!(compress_filesystem_improved(TEST).unwrap(), 2858);
+    }
+
+    #[test]
+    fn parse_rejects_non_digits() {
+        let error = DiskMap::try_from(String::from("12\n3a")).err().unwrap();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 3, "\n")
+        );
+    }
+
+    fn reference_blocks(input: &str) -> Vec<Option<u64>> {
+        input
+            .chars()
+            .enumerate()
+            .flat_map(|(i, c)| {
+                let file = (i % 2 == 0).then_some(i as u64 / 2);
+                vec![file; c.to_digit(10).unwrap() as usize]
+            })
+            .collect()
+    }
+
+    fn reference_checksum(blocks: &[Option<u64>]) -> u64 {
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, file)| i as u64 * file.unwrap_or(0))
+            .sum()
+    }
+
+    fn reference_part_1(input: &str) -> u64 {
+        let mut blocks = reference_blocks(input);
+        loop {
+            let free = blocks.iter().position(|b| b.is_none());
+            let last = blocks.iter().rposition(|b| b.is_some());
+            match (free, last) {
+                (Some(free), Some(last)) if free < last => blocks.swap(free, last),
+                _ => break,
+            }
+        }
+        reference_checksum(&blocks)
+    }
+
+    fn reference_part_2(input: &str) -> u64 {
+        let mut blocks = reference_blocks(input);
+        let files = blocks.iter().flatten().max().copied().unwrap_or(0);
+        for file in (0..=files).rev() {
+            let start = blocks.iter().position(|b| *b == Some(file)).unwrap();
+            let size = blocks.iter().filter(|b| **b == Some(file)).count();
+            let free = (0..start).find(|&i| blocks[i..i + size].iter().all(|b| b.is_none()));
+            if let Some(free) = free {
+                for i in 0..size {
+                    blocks.swap(free + i, start + i);
+                }
+            }
+        }
+        reference_checksum(&blocks)
+    }
+
+    #[test]
+    fn matches_reference_solvers() {
+        differential::check(&Day9, 20, reference_part_1, reference_part_2);
+    }
 }
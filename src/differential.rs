@@ -0,0 +1,104 @@
+//! Differential testing of the solvers against simple reference implementations, on inputs from
+//! each day's generator.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{answer::Answer, solution::Solver};
+
+const SEEDS: u64 = 30;
+
+/// Checks both parts of `solver` against the reference solvers on inputs generated with `size`.
+/// A mismatch is shrunk to a minimal input that still disagrees before failing the test.
+pub fn check<A: Into<Answer>, B: Into<Answer>>(
+    solver: &dyn Solver,
+    size: usize,
+    part_1: fn(&str) -> A,
+    part_2: fn(&str) -> B,
+) {
+    let part_1 = |input: &str| part_1(input).into();
+    let part_2 = |input: &str| part_2(input).into();
+    let references: [&dyn Fn(&str) -> Answer; 2] = [&part_1, &part_2];
+    for seed in 0..SEEDS {
+        let input = solver.generate(size, seed);
+        for (part, reference) in (1..=2).zip(references) {
+            // Inputs the solver rejects, or panics on while shrinking, never count as failures
+            let disagrees = |input: &str| matches!(solver.solve(part, input.to_string()), Ok(answer) if answer != reference(input));
+            if disagrees(&input) {
+                let input = shrink(&input, skipping_panics(disagrees));
+                panic!(
+                    "day {} part {} disagrees with its reference on seed {}: expected {} but got {} for\n{}",
+                    solver.day(),
+                    part,
+                    seed,
+                    reference(&input),
+                    solver.solve(part, input.clone()).unwrap(),
+                    input
+                );
+            }
+        }
+    }
+}
+
+/// Wraps `fails` so that a candidate it panics on, in the solver or the reference, is skipped
+/// rather than ending the whole test with a panic unrelated to the original failure.
+fn skipping_panics(fails: impl Fn(&str) -> bool) -> impl Fn(&str) -> bool {
+    move |input| panic::catch_unwind(AssertUnwindSafe(|| fails(input))).unwrap_or(false)
+}
+
+/// Removes lines, then characters within each line, for as long as the input keeps failing.
+pub fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut lines = input.lines().map(String::from).collect::<Vec<_>>();
+    remove_chunks(&mut lines, |lines| fails(&lines.join("\n")));
+    for i in 0..lines.len() {
+        let mut chars = lines[i].chars().collect::<Vec<_>>();
+        remove_chunks(&mut chars, |chars| {
+            let mut candidate = lines.clone();
+            candidate[i] = chars.iter().collect();
+            fails(&candidate.join("\n"))
+        });
+        lines[i] = chars.into_iter().collect();
+    }
+    lines.join("\n")
+}
+
+/// Tries removing runs of items, halving the run length down to single items, and keeps every
+/// removal after which the items still fail.
+fn remove_chunks<T: Clone>(items: &mut Vec<T>, fails: impl Fn(&[T]) -> bool) {
+    let mut chunk = items.len().div_ceil(2).max(1);
+    loop {
+        let mut start = 0;
+        while start < items.len() {
+            let end = (start + chunk).min(items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+            if fails(&candidate) {
+                *items = candidate;
+            } else {
+                start = end;
+            }
+        }
+        if chunk == 1 {
+            break;
+        }
+        chunk /= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrink_keeps_only_what_fails() {
+        let fails = |input: &str| input.lines().any(|line| line.contains("xy"));
+        assert_eq!(shrink("abc\nd\naxyz\nxq\nyy", fails), "xy");
+    }
+
+    #[test]
+    fn shrink_skips_candidates_that_panic() {
+        let fails = |input: &str| {
+            assert!(input.contains('a'), "no a in {:?}", input);
+            input.contains('x')
+        };
+        assert_eq!(shrink("abc\nxyz", skipping_panics(fails)), "a\nx");
+    }
+}
@@ -0,0 +1,106 @@
+use std::{error::Error, fmt::Display, path::PathBuf};
+
+/// Error raised when a day's input does not match the puzzle's format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, line: usize, column: usize, text: &str, reason: impl Display) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            text: text.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Builds an error for `text`, which must be a slice of `input`, locating it by its offset.
+    pub fn at(day: u8, input: &str, text: &str, reason: impl Display) -> Self {
+        let offset = (text.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rfind('\n').map_or(offset, |i| offset - i - 1) + 1;
+        Self::new(day, line, column, text, reason)
+    }
+
+    /// Moves an error located within a single line onto line `line` of the whole input.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+        self
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}: {} at line {}, column {}: {:?}",
+            self.day, self.reason, self.line, self.column, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Error raised when a day's input file is not where the input resolver expects it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingInputError {
+    pub day: u8,
+    pub path: PathBuf,
+}
+
+impl Display for MissingInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}: no input file at {}",
+            self.day,
+            self.path.display()
+        )
+    }
+}
+
+impl Error for MissingInputError {}
+
+/// Error returned by a part that has not been solved yet, as scaffolded by `AOC24 new`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsolvedError {
+    pub day: u8,
+    pub part: u8,
+}
+
+impl Display for UnsolvedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {}: part {} is not solved yet", self.day, self.part)
+    }
+}
+
+impl Error for UnsolvedError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn at_locates_text_by_offset() {
+        let input = "1 2\n3 x 4\n";
+        let error = ParseError::at(1, input, &input[6..7], "bad");
+        assert_eq!((error.line, error.column, error.text.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn on_line_moves_single_line_error() {
+        let line = "12: 3 y";
+        let error = ParseError::at(7, line, &line[6..], "bad").on_line(4);
+        assert_eq!((error.line, error.column), (4, 7));
+    }
+}
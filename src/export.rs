@@ -0,0 +1,121 @@
+use std::io::Write;
+
+use crate::{
+    answer::Answer,
+    bench::Timer,
+    memory::{kb, MemoryProbe},
+};
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    Json,
+    Csv,
+    Table,
+}
+
+/// Outcome of running one part of a day on one input. Memory figures are left out when solvers
+/// ran in parallel, since the allocator counters are shared by every thread.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: Answer,
+    pub parse_us: u64,
+    pub solve_us: u64,
+    pub peak_bytes: Option<usize>,
+    pub allocations: Option<usize>,
+    pub allocated_bytes: Option<usize>,
+}
+
+impl Record {
+    pub fn new(
+        day: u8,
+        part: u8,
+        input: &str,
+        answer: Answer,
+        timer: &Timer,
+        memory: Option<&MemoryProbe>,
+    ) -> Self {
+        let micros = |samples: &[std::time::Duration]| {
+            samples.iter().map(|d| d.as_micros() as u64).sum::<u64>()
+        };
+        Self {
+            day,
+            part,
+            input: input.to_string(),
+            answer,
+            parse_us: micros(&timer.parse),
+            solve_us: micros(&timer.solve),
+            peak_bytes: memory.map(|m| m.peak),
+            allocations: memory.map(|m| m.parse.allocations + m.solve.allocations),
+            allocated_bytes: memory.map(|m| m.parse.allocated + m.solve.allocated),
+        }
+    }
+}
+
+pub fn write_records(records: &[Record], format: Format, mut out: impl Write) -> Result<()> {
+    match format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut out, records)?;
+            writeln!(out)?;
+        }
+        Format::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+        }
+        Format::Table => write_table(records, out)?,
+    }
+    Ok(())
+}
+
+fn write_table(records: &[Record], mut out: impl Write) -> Result<()> {
+    let header = [
+        "day",
+        "part",
+        "input",
+        "answer",
+        "parse us",
+        "solve us",
+        "peak kb",
+        "allocations",
+    ];
+    let rows = records
+        .iter()
+        .map(|r| {
+            [
+                r.day.to_string(),
+                r.part.to_string(),
+                r.input.clone(),
+                r.answer.to_string(),
+                r.parse_us.to_string(),
+                r.solve_us.to_string(),
+                r.peak_bytes
+                    .map_or(String::from("-"), |b| format!("{:.1}", kb(b))),
+                r.allocations.map_or(String::from("-"), |a| a.to_string()),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, h)| rows.iter().map(|r| r[i].len()).fold(h.len(), usize::max))
+        .collect::<Vec<_>>();
+    let header = header.map(String::from);
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+//! Fuzz tests feeding random and mutated inputs to every day's parser, which must either accept
+//! them or return an error, but never panic or hang.
+
+use std::{sync::mpsc, thread, time::Duration};
+
+use crate::{differential::shrink, generate::Rng, solution::Solver, SOLUTIONS};
+
+const CASES: u64 = 300;
+const TIMEOUT: Duration = Duration::from_secs(5);
+const CHARACTERS: &[char] = &[
+    '0', '1', '5', '9', '-', '+', 'a', 'X', 'M', 'A', 'S', '^', '>', '#', '.', '|', ',', ':', '(',
+    ')', ' ', '\t', '\n', '\r', 'é', '\u{feff}',
+];
+
+/// How running a parser on an input went, `None` meaning it panicked or did not finish in time.
+fn parse_outcome(solver: &'static dyn Solver, input: &str) -> Option<bool> {
+    let (sender, receiver) = mpsc::channel();
+    let input = input.to_string();
+    thread::spawn(move || {
+        let _ = sender.send(solver.validate(input).is_ok());
+    });
+    receiver.recv_timeout(TIMEOUT).ok()
+}
+
+fn check_parser(solver: &'static dyn Solver, input: &str) {
+    if parse_outcome(solver, input).is_none() {
+        let input = shrink(input, |input| parse_outcome(solver, input).is_none());
+        panic!(
+            "day {} parser panicked or hung on {:?}",
+            solver.day(),
+            input
+        );
+    }
+}
+
+fn random_input(rng: &mut Rng) -> String {
+    (0..rng.range(0..=200))
+        .map(|_| *rng.pick(CHARACTERS))
+        .collect()
+}
+
+/// Applies a few random edits to `input`, from single characters to whole lines.
+fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut chars = input.chars().collect::<Vec<_>>();
+    for _ in 0..rng.range(1..=5) {
+        let at = rng.range(0..=chars.len() as u64) as usize;
+        match rng.range(0..=6) {
+            0 => chars.insert(at, *rng.pick(CHARACTERS)),
+            1 if at < chars.len() => {
+                chars.remove(at);
+            }
+            2 if at < chars.len() => chars[at] = *rng.pick(CHARACTERS),
+            3 => chars.truncate(at),
+            4 => chars.extend(rng.pick(&["\n", "\r\n", "\n\n", " "]).chars()),
+            5 => {
+                let number = rng.pick(&["99999999999999999999999", "-1", "0", ""]);
+                chars.splice(at..at, number.chars());
+            }
+            _ => {
+                let text = chars.iter().collect::<String>();
+                let mut lines = text.lines().collect::<Vec<_>>();
+                if !lines.is_empty() {
+                    let line = rng.range(0..=lines.len() as u64 - 1) as usize;
+                    if rng.chance(0.5) {
+                        lines.insert(line, lines[line]);
+                    } else {
+                        lines.remove(line);
+                    }
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[test]
+fn parsers_survive_random_input() {
+    let mut rng = Rng::new(0);
+    for &solver in SOLUTIONS {
+        for _ in 0..CASES {
+            check_parser(solver, &random_input(&mut rng));
+        }
+    }
+}
+
+#[test]
+fn parsers_survive_mutated_input() {
+    let mut rng = Rng::new(1);
+    for &solver in SOLUTIONS {
+        for seed in 0..CASES {
+            let input = solver.generate(rng.range(1..=10) as usize, seed);
+            check_parser(solver, &mutate(&input, &mut rng));
+        }
+    }
+}
+
+#[test]
+fn parsers_survive_edge_cases() {
+    for &solver in SOLUTIONS {
+        for input in ["", "\n", "\r\n", " ", "\u{feff}", "\n\n\n", "0", "é"] {
+            check_parser(solver, input);
+        }
+        let example = solver.generate(5, 0);
+        check_parser(solver, &format!("{}\n", example));
+        check_parser(solver, &example.replace('\n', "\r\n"));
+    }
+}
@@ -0,0 +1,103 @@
+use std::ops::RangeInclusive;
+
+/// Seeded SplitMix64 generator for building random puzzle inputs. It is implemented here rather
+/// than taken from a crate so the same seed produces the same input on every version.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Value in `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (low, high) = range.into_inner();
+        assert!(low <= high, "cannot pick from an empty range");
+        match (high - low).checked_add(1) {
+            Some(len) => low + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// True with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() as u64 - 1) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i as u64) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_solution, UnsolvedError, SOLUTIONS};
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        assert!((0..100).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn range_stays_in_bounds() {
+        let mut rng = Rng::new(7);
+        assert!((0..1000).all(|_| (3..=9).contains(&rng.range(3..=9))));
+        assert_eq!(rng.range(5..=5), 5);
+    }
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for solution in SOLUTIONS {
+            for size in [1, 12] {
+                for seed in 0..3 {
+                    let input = solution.generate(size, seed);
+                    for part in [1, 2] {
+                        match solution.solve(part, input.clone()) {
+                            // Freshly scaffolded days have nothing to solve with yet
+                            Err(e) if e.is::<UnsolvedError>() => {}
+                            Err(e) => panic!(
+                                "day {} part {} failed on size {} seed {}: {}\n{}",
+                                solution.day(),
+                                part,
+                                size,
+                                seed,
+                                e,
+                                input
+                            ),
+                            Ok(_) => {}
+                        }
+                    }
+                }
+            }
+        }
+        // Disk maps whose gaps are all empty leave day 9 nothing to compress
+        let day_9 = find_solution(9).unwrap();
+        for input in ["10", "10101", "302"] {
+            for part in [1, 2] {
+                let answer = day_9.solve(part, input.to_string());
+                assert!(answer.is_ok(), "day 9 part {} failed on {}", part, input);
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::ParseError,
+    point::{Direction, Point},
+};
+
+/// Rectangular grid stored as one row-major `Vec`. Positions may be out of bounds, in which case
+/// the checked accessors return `None`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "grid cells must fill every row"
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one cell per character of each line, rejecting lines of different lengths.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        mut cell: impl FnMut(char) -> T,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in input.trim_end_matches(['\n', '\r']).lines() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let line_width = cells.len() - len;
+            if *width.get_or_insert(line_width) != line_width {
+                return Err(ParseError::at(
+                    day,
+                    input,
+                    line,
+                    format!("expected {} cells in every row", width.unwrap()),
+                ));
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    /// Like [`Grid::parse`], but reads one cell per byte of each line, without decoding
+    /// characters. Puzzle grids are ASCII, so the cells are copied straight from the input into a
+    /// single allocation, and non-ASCII lines are rejected.
+    pub fn parse_bytes(
+        day: u8,
+        input: &str,
+        mut cell: impl FnMut(u8) -> T,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for line in input.trim_end_matches(['\n', '\r']).lines() {
+            if !line.is_ascii() {
+                return Err(ParseError::at(day, input, line, "expected ASCII cells"));
+            }
+            if *width.get_or_insert(line.len()) != line.len() {
+                return Err(ParseError::at(
+                    day,
+                    input,
+                    line,
+                    format!("expected {} cells in every row", width.unwrap()),
+                ));
+            }
+            cells.extend(line.bytes().map(&mut cell));
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Point) -> bool {
+        (0..self.width as i64).contains(&position.x)
+            && (0..self.height as i64).contains(&position.y)
+    }
+
+    fn index_of(&self, position: Point) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y as usize * self.width + position.x as usize)
+    }
+
+    fn position_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    pub fn get(&self, position: Point) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    /// Every cell with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// Positions of the in-bounds cells above, right, below and left of `position`.
+    pub fn neighbors(&self, position: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors_in(position, &Direction::CARDINAL)
+    }
+
+    /// Positions of the in-bounds cells around `position`, including diagonals.
+    pub fn all_neighbors(&self, position: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors_in(position, &Direction::ALL)
+    }
+
+    fn neighbors_in<'a>(
+        &'a self,
+        position: Point,
+        directions: &'a [Direction],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .map(move |&direction| position + direction)
+            .filter(|&p| self.contains(p))
+    }
+
+    /// Cells from `start` moving by `step` each time, until leaving the grid.
+    pub fn line(&self, start: Point, step: Point) -> impl Iterator<Item = &T> {
+        (0..)
+            .map(move |n| start + step * n)
+            .map_while(|p| self.get(p))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width as i64).map(move |x| self.line(Point::new(x, 0), Direction::South.delta()))
+    }
+
+    /// Every diagonal running down and to the right, starting from the bottom left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height as i64)
+            .rev()
+            .map(|y| Point::new(0, y))
+            .chain((1..self.width as i64).map(|x| Point::new(x, 0)));
+        starts.map(move |start| self.line(start, Direction::SouthEast.delta()))
+    }
+
+    /// Every diagonal running down and to the left, starting from the top left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_x = self.width as i64 - 1;
+        let starts = (0..self.width as i64)
+            .map(|x| Point::new(x, 0))
+            .chain((1..self.height as i64).map(move |y| Point::new(last_x, y)));
+        starts.map(move |start| self.line(start, Direction::SouthWest.delta()))
+    }
+
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.position_of(i))
+    }
+
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter_map(move |(position, cell)| predicate(cell).then_some(position))
+    }
+
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(Point, &T)> {
+        self.iter().find(|(_, cell)| predicate(cell))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, position: Point) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse(0, "abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.to_string(), "abc\ndef");
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let error = Grid::parse(0, "abc\nde\n", |c| c).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn parse_bytes_matches_parse() {
+        let bytes = Grid::parse_bytes(0, "abc\r\ndef\n", |b| b).unwrap();
+        assert_eq!(bytes.map(|&b| char::from(b)), grid());
+        let error = Grid::parse_bytes(0, "abc\ndé\n", |b| b).unwrap_err();
+        assert_eq!(
+            (error.line, error.reason.as_str()),
+            (2, "expected ASCII cells")
+        );
+        let error = Grid::parse_bytes(0, "ab\nabc", |b| b).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn get_is_bounds_checked() {
+        let grid = grid();
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point::new(3, 1)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid[Point::new(1, 0)], 'b');
+    }
+
+    #[test]
+    fn neighbors_stay_in_bounds() {
+        let grid = grid();
+        let neighbors = grid.neighbors(Point::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(neighbors, [Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.all_neighbors(Point::new(1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn lines_of_cells() {
+        let grid = grid();
+        let collect = |it: &mut dyn Iterator<Item = &char>| it.collect::<String>();
+        let columns = grid
+            .columns()
+            .map(|mut c| collect(&mut c))
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        let diagonals = grid
+            .diagonals()
+            .map(|mut d| collect(&mut d))
+            .collect::<Vec<_>>();
+        assert_eq!(diagonals, ["d", "ae", "bf", "c"]);
+        let anti = grid
+            .anti_diagonals()
+            .map(|mut d| collect(&mut d))
+            .collect::<Vec<_>>();
+        assert_eq!(anti, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn find_and_position() {
+        let grid = grid();
+        assert_eq!(grid.position(|&c| c == 'e'), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(|&c| c > 'd'), Some((Point::new(1, 1), &'e')));
+        let positions = grid.positions(|&c| c < 'c').collect::<Vec<_>>();
+        assert_eq!(positions, [Point::new(0, 0), Point::new(1, 0)]);
+    }
+}
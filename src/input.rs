@@ -0,0 +1,214 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, Read, Result},
+    path::{Path, PathBuf},
+};
+
+use crate::error::MissingInputError;
+
+/// Environment variable naming the directory that holds the puzzle inputs.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub const DEFAULT_INPUT_DIR: &str = "inputs";
+
+/// Where a day's puzzle input is read from. A `&str` converts to [`Input::Path`], so solvers can
+/// still be called with a file path.
+pub enum Input<'a> {
+    /// Path to a file, or `-` for stdin.
+    Path(&'a Path),
+    /// The puzzle input itself.
+    Text(&'a str),
+    /// Any reader, read until the end.
+    Reader(Box<dyn Read + 'a>),
+}
+
+impl<'a> Input<'a> {
+    pub fn reader(reader: impl Read + 'a) -> Self {
+        Self::Reader(Box::new(reader))
+    }
+
+    /// Reads the whole input and [`normalize`]s it.
+    pub fn read(self) -> Result<String> {
+        let mut buf = String::new();
+        match self {
+            Self::Path(path) if path == Path::new("-") => {
+                io::stdin().lock().read_to_string(&mut buf)?;
+            }
+            Self::Path(path) => {
+                File::open(path)?.read_to_string(&mut buf)?;
+            }
+            Self::Text(text) => buf.push_str(text),
+            Self::Reader(mut reader) => {
+                reader.read_to_string(&mut buf)?;
+            }
+        }
+        Ok(normalize(&buf))
+    }
+}
+
+impl<'a> From<&'a str> for Input<'a> {
+    fn from(path: &'a str) -> Self {
+        Self::Path(Path::new(path))
+    }
+}
+
+impl<'a> From<&'a Path> for Input<'a> {
+    fn from(path: &'a Path) -> Self {
+        Self::Path(path)
+    }
+}
+
+/// Locates each day's input files. Puzzle inputs are read from the input directory, or from a
+/// subdirectory named after the user when a personal input set is chosen. Examples are the same
+/// for everyone and always live in its `examples` subdirectory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputResolver {
+    dir: PathBuf,
+    user: Option<String>,
+}
+
+impl InputResolver {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            user: None,
+        }
+    }
+
+    /// Uses the directory named by [`INPUT_DIR_VAR`] when it is set, and [`DEFAULT_INPUT_DIR`]
+    /// otherwise.
+    pub fn from_env() -> Self {
+        Self::new(env::var_os(INPUT_DIR_VAR).unwrap_or_else(|| DEFAULT_INPUT_DIR.into()))
+    }
+
+    pub fn with_user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        let dir = match &self.user {
+            Some(user) => self.dir.join(user),
+            None => self.dir.clone(),
+        };
+        dir.join(format!("day_{}.txt", day))
+    }
+
+    pub fn example_path(&self, day: u8) -> PathBuf {
+        self.dir.join("examples").join(format!("day_{}.txt", day))
+    }
+
+    /// Path of the day's puzzle input, which must exist.
+    pub fn input(&self, day: u8) -> std::result::Result<PathBuf, MissingInputError> {
+        Self::existing(day, self.input_path(day))
+    }
+
+    /// Path of the day's example input, which must exist.
+    pub fn example(&self, day: u8) -> std::result::Result<PathBuf, MissingInputError> {
+        Self::existing(day, self.example_path(day))
+    }
+
+    fn existing(day: u8, path: PathBuf) -> std::result::Result<PathBuf, MissingInputError> {
+        if path.is_file() {
+            Ok(path)
+        } else {
+            Err(MissingInputError { day, path })
+        }
+    }
+}
+
+impl Default for InputResolver {
+    fn default() -> Self {
+        Self::new(DEFAULT_INPUT_DIR)
+    }
+}
+
+/// Puts input into the form every parser expects, whichever platform it was saved on: no UTF-8
+/// byte order mark, `\n` line endings and no trailing blank lines or final newline.
+pub fn normalize(input: &str) -> String {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut lines = input.lines().collect::<Vec<_>>();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+pub fn get_input<'a>(input: impl Into<Input<'a>>) -> Result<String> {
+    input.into().read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnsolvedError;
+
+    #[test]
+    fn every_source_reads_the_same_input() {
+        let path = "inputs/examples/day_11.txt";
+        let text = get_input(path).unwrap();
+        assert_eq!(get_input(Input::Text(&text)).unwrap(), text);
+        assert_eq!(get_input(Input::reader(text.as_bytes())).unwrap(), text);
+    }
+
+    #[test]
+    fn normalize_line_endings_bom_and_trailing_lines() {
+        assert_eq!(normalize("a\r\nb\r\n\r\nc"), "a\nb\n\nc");
+        assert_eq!(normalize("\u{feff}12 34\n"), "12 34");
+        assert_eq!(normalize("a\nb\n\n  \n\t\n"), "a\nb");
+        assert_eq!(normalize("\r\n\n"), "");
+        assert_eq!(normalize("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn every_day_solves_crlf_input_like_lf_input() {
+        for solver in crate::SOLUTIONS {
+            let lf = get_input(solver.test()).unwrap();
+            let crlf = format!("\u{feff}{}\r\n\r\n", lf.replace('\n', "\r\n"));
+            assert_eq!(get_input(Input::Text(&crlf)).unwrap(), lf);
+            for part in [1, 2] {
+                let expected = match solver.solve(part, lf.clone()) {
+                    // Freshly scaffolded days have nothing to compare yet
+                    Err(e) if e.is::<UnsolvedError>() => continue,
+                    answer => answer.unwrap(),
+                };
+                assert_eq!(
+                    solver
+                        .solve(part, get_input(Input::Text(&crlf)).unwrap())
+                        .unwrap(),
+                    expected,
+                    "day {} part {} differs between LF and CRLF input",
+                    solver.day(),
+                    part
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        assert!(get_input("inputs/examples/missing.txt").is_err());
+    }
+
+    #[test]
+    fn resolver_matches_default_paths() {
+        let resolver = InputResolver::default();
+        assert_eq!(resolver.input_path(7), Path::new(crate::day_7::INPUT));
+        assert_eq!(resolver.example(7).unwrap(), Path::new(crate::day_7::TEST));
+    }
+
+    #[test]
+    fn resolver_uses_user_input_sets() {
+        let resolver = InputResolver::new("puzzles").with_user("alice");
+        assert_eq!(resolver.input_path(3), Path::new("puzzles/alice/day_3.txt"));
+        assert_eq!(
+            resolver.example_path(3),
+            Path::new("puzzles/examples/day_3.txt")
+        );
+        let error = resolver.input(3).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 3: no input file at puzzles/alice/day_3.txt"
+        );
+    }
+}
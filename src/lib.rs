@@ -0,0 +1,39 @@
+//! Advent of Code 2024 solutions. Each `day_N` module exposes its puzzle's solver functions and a
+//! [`Solution`] implementation, and [`SOLUTIONS`] lists every solved day.
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod bitset;
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+#[cfg(test)]
+mod differential;
+pub mod error;
+pub mod export;
+#[cfg(test)]
+mod fuzz;
+pub mod generate;
+pub mod grid;
+pub mod input;
+pub mod memory;
+pub mod parse;
+pub mod point;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+
+pub use answer::Answer;
+pub use error::{MissingInputError, ParseError, UnsolvedError};
+pub use input::{get_input, normalize, Input, InputResolver};
+pub use solution::{find_solution, Solution, Solver, SOLUTIONS};
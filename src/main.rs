@@ -1,39 +1,404 @@
-#![allow(dead_code)]
-
-use chrono::prelude::Local;
-use peak_alloc::PeakAlloc;
+use anyhow::{bail, Context};
+use aoc24::{
+    answers::{self, Answers, ANSWERS},
+    bench::{self, Timer},
+    export::{self, Format, Record},
+    input::{get_input, InputResolver},
+    memory::{self, CountingAlloc, MemoryProbe},
+    scaffold,
+    solution::{self, Solver},
+};
+use clap::{Args, Parser, Subcommand};
+use rayon::prelude::*;
 use std::{
-    fs::File,
-    io::{Read, Result},
+    fs, io,
+    path::{Path, PathBuf},
+    time::Instant,
 };
 
-mod day_1;
-mod day_10;
-mod day_11;
-mod day_12;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-mod day_7;
-mod day_8;
-mod day_9;
-
 #[global_allocator]
-static PEAK_ALLOC: PeakAlloc = PeakAlloc;
+static ALLOC: CountingAlloc = CountingAlloc::new();
+
+#[derive(Parser)]
+#[command(version, about = "Advent of Code 2024")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one part of a day's puzzle
+    Run(RunArgs),
+    /// Time the parse and solve phases of solvers over many runs
+    Bench(BenchArgs),
+    /// Check every solver against the answers stored in answers.toml
+    Verify(VerifyArgs),
+    /// List every solved day
+    List,
+    /// Generate and register the module and input files for a new day
+    New(NewArgs),
+    /// Write a random input for a day, for stress testing its solver
+    Generate(GenerateArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day of the puzzle to run
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+    /// Run every solved day, in parallel unless --serial is given
+    #[arg(short, long, conflicts_with = "input")]
+    all: bool,
+    /// Run one solver at a time so timings are not skewed and memory can be measured
+    #[arg(short, long, requires = "all")]
+    serial: bool,
+    /// Part of the puzzle to run, both parts when omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Print the results as machine readable records instead of a report
+    #[arg(short, long)]
+    format: Option<Format>,
+    /// Store the answers in answers.toml for later verification
+    #[arg(short, long)]
+    record: bool,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Verify the days' test inputs instead of their puzzle inputs
+    #[arg(short, long)]
+    test: bool,
+    #[command(flatten)]
+    dirs: InputDirArgs,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    /// Day of the puzzle to create
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Title of the puzzle
+    #[arg(short, long, default_value = "Untitled")]
+    title: String,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day of the puzzle to generate an input for
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Size of the input: its number of lines or records, or the side of its grid
+    #[arg(short = 'n', long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    size: u64,
+    /// Seed of the random generator, the same seed always gives the same input
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+    /// File to write the input to instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day of the puzzle to benchmark, every solved day when omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Part of the puzzle to benchmark, both parts when omitted
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Number of unmeasured runs before measuring
+    #[arg(short, long, default_value_t = 3)]
+    warmup: usize,
+    /// Number of measured runs
+    #[arg(short = 'n', long, default_value_t = 20)]
+    iterations: usize,
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Args)]
+struct InputArgs {
+    /// Input file to use instead of the day's default input, or - to read stdin
+    #[arg(short, long, conflicts_with = "test")]
+    input: Option<String>,
+    /// Use the day's test input instead of its puzzle input
+    #[arg(short, long)]
+    test: bool,
+    #[command(flatten)]
+    dirs: InputDirArgs,
+}
+
+impl InputArgs {
+    fn path(&self, solution: &dyn Solver) -> anyhow::Result<PathBuf> {
+        Ok(match &self.input {
+            Some(path) => PathBuf::from(path),
+            None if self.test => self.dirs.resolver().example(solution.day())?,
+            None => self.dirs.resolver().input(solution.day())?,
+        })
+    }
+}
+
+#[derive(Args)]
+struct InputDirArgs {
+    /// Directory holding the inputs, instead of $AOC_INPUT_DIR or inputs
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+    /// Use this user's puzzle inputs, kept in a subdirectory of the input directory
+    #[arg(short, long)]
+    user: Option<String>,
+}
+
+impl InputDirArgs {
+    fn resolver(&self) -> InputResolver {
+        let resolver = match &self.input_dir {
+            Some(dir) => InputResolver::new(dir),
+            None => InputResolver::from_env(),
+        };
+        match &self.user {
+            Some(user) => resolver.with_user(user),
+            None => resolver,
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) => run(&args)?,
+        Command::Bench(args) => run_bench(&args)?,
+        Command::Verify(args) => verify(&args)?,
+        Command::List => {
+            for solution in solution::SOLUTIONS {
+                println!("Day {:>2}: {}", solution.day(), solution.title());
+            }
+        }
+        Command::Generate(args) => generate(&args)?,
+        Command::New(args) => {
+            for path in scaffold::new_day(Path::new("."), args.day, &args.title)? {
+                println!("Created {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn generate(args: &GenerateArgs) -> anyhow::Result<()> {
+    let Some(solution) = solution::find_solution(args.day) else {
+        bail!("Day {} is not solved", args.day);
+    };
+    let input = solution.generate(args.size as usize, args.seed);
+    match &args.output {
+        Some(path) => {
+            fs::write(path, input).with_context(|| format!("cannot write {}", path.display()))?
+        }
+        None => println!("{}", input),
+    }
+    Ok(())
+}
+
+fn read_input(path: &Path) -> anyhow::Result<String> {
+    get_input(path).with_context(|| format!("cannot read {}", path.display()))
+}
+
+struct Measurement {
+    record: Record,
+    memory: Option<MemoryProbe>,
+    hash: String,
+}
+
+fn run(args: &RunArgs) -> anyhow::Result<()> {
+    let solutions = match args.day {
+        Some(day) => match solution::find_solution(day) {
+            Some(solution) => vec![solution],
+            None => bail!("Day {} is not solved", day),
+        },
+        None => solution::SOLUTIONS.to_vec(),
+    };
+    let parts = args.part.map_or(vec![1, 2], |part| vec![part]);
+    let inputs = solutions
+        .iter()
+        .map(|&solution| {
+            let path = args.input.path(solution)?;
+            let input = read_input(&path)?;
+            Ok((solution, path.display().to_string(), input))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let jobs = inputs
+        .iter()
+        .flat_map(|(solution, path, input)| {
+            parts
+                .iter()
+                .map(move |&part| (*solution, part, path.as_str(), input.as_str()))
+        })
+        .collect::<Vec<_>>();
+    let start = Instant::now();
+    let measurements = if args.all && !args.serial {
+        jobs.par_iter()
+            .map(|&(solution, part, path, input)| measure(solution, part, path, input, false))
+            .collect::<anyhow::Result<Vec<_>>>()?
+    } else {
+        jobs.iter()
+            .map(|&(solution, part, path, input)| measure(solution, part, path, input, true))
+            .collect::<anyhow::Result<Vec<_>>>()?
+    };
+    let wall_time = start.elapsed();
+
+    if args.record {
+        let mut answers = Answers::load(ANSWERS)?;
+        for m in &measurements {
+            let r = &m.record;
+            answers.insert(r.day, r.part, m.hash.clone(), &r.input, &r.answer);
+        }
+        answers.save(ANSWERS)?;
+    }
+    let records = measurements
+        .into_iter()
+        .map(|m| {
+            if args.format.is_none() && !args.all {
+                print_report(&m.record, m.memory.as_ref(), parts.len() > 1);
+            }
+            m.record
+        })
+        .collect::<Vec<_>>();
+    match args.format {
+        Some(format) => export::write_records(&records, format, io::stdout().lock())?,
+        None if args.all => export::write_records(&records, Format::Table, io::stdout().lock())?,
+        None => {}
+    }
+    if args.all {
+        eprintln!("Total wall time: {} us", wall_time.as_micros());
+    }
+    Ok(())
+}
+
+fn measure(
+    solution: &dyn Solver,
+    part: u8,
+    path: &str,
+    input: &str,
+    with_memory: bool,
+) -> anyhow::Result<Measurement> {
+    let hash = answers::hash_input(input);
+    let input = input.to_string();
+    let mut timer = Timer::default();
+    let (answer, memory) = if with_memory {
+        let mut probe = (timer, MemoryProbe::default());
+        let answer = solution.solve_with(part, input, &mut probe)?;
+        timer = probe.0;
+        (answer, Some(probe.1))
+    } else {
+        (solution.solve_with(part, input, &mut timer)?, None)
+    };
+    let record = Record::new(solution.day(), part, path, answer, &timer, memory.as_ref());
+    Ok(Measurement {
+        record,
+        memory,
+        hash,
+    })
+}
+
+fn print_report(record: &Record, memory: Option<&MemoryProbe>, with_part: bool) {
+    if with_part {
+        println!("Part {}", record.part);
+    }
+    println!("Result: {}", record.answer);
+    println!("Elapsed time: {} us", record.parse_us + record.solve_us);
+    if let Some(memory) = memory {
+        println!("Memory Used: {} kb", memory::kb(memory.peak));
+        println!("  parse  {} us, {}", record.parse_us, memory.parse);
+        println!("  solve  {} us, {}", record.solve_us, memory.solve);
+    }
+}
 
-fn main() {
-    let start = Local::now();
-    let res = day_12::find_fencing_price(day_12::INPUT).unwrap();
-    let duration = (Local::now() - start).num_microseconds().unwrap();
-    println!("Result: {}", res);
-    println!("Elapsed time: {} us", duration);
-    println!("Memory Used: {} kb", PEAK_ALLOC.peak_usage_as_kb());
+fn verify(args: &VerifyArgs) -> anyhow::Result<()> {
+    let answers = Answers::load(ANSWERS)?;
+    let (mut checked, mut failed) = (0, 0);
+    for solution in solution::SOLUTIONS {
+        let resolver = args.dirs.resolver();
+        let path = if args.test {
+            resolver.example(solution.day())
+        } else {
+            resolver.input(solution.day())
+        };
+        let input = match path {
+            Ok(path) => read_input(&path),
+            Err(e) => {
+                let path = e.path.display();
+                println!(
+                    "Day {:>2}: skipped, no input file at {}",
+                    solution.day(),
+                    path
+                );
+                continue;
+            }
+        };
+        let input = match input {
+            Ok(input) => input,
+            Err(e) => {
+                println!("Day {:>2}: skipped, {:#}", solution.day(), e);
+                continue;
+            }
+        };
+        let hash = answers::hash_input(&input);
+        let solved = solution
+            .solve_both(input)
+            .map(|(part_1, part_2)| [part_1, part_2]);
+        for part in [1, 2] {
+            let Some(expected) = answers.get(solution.day(), part, &hash) else {
+                println!("Day {:>2} part {}: no stored answer", solution.day(), part);
+                continue;
+            };
+            checked += 1;
+            match &solved {
+                Ok(answers) if answers[part as usize - 1] == *expected => {
+                    println!("Day {:>2} part {}: ok", solution.day(), part)
+                }
+                Ok(answers) => {
+                    failed += 1;
+                    println!(
+                        "Day {:>2} part {}: expected {} but got {}",
+                        solution.day(),
+                        part,
+                        expected,
+                        answers[part as usize - 1]
+                    );
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("Day {:>2} part {}: failed: {}", solution.day(), part, e);
+                }
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{} of {} answers differ from {}", failed, checked, ANSWERS);
+    }
+    Ok(())
 }
 
-pub fn get_input(path: &str) -> Result<String> {
-    let mut buf = String::new();
-    File::open(path)?.read_to_string(&mut buf)?;
-    Ok(buf)
+fn run_bench(args: &BenchArgs) -> anyhow::Result<()> {
+    let solutions = match args.day {
+        Some(day) => match solution::find_solution(day) {
+            Some(solution) => vec![solution],
+            None => bail!("Day {} is not solved", day),
+        },
+        None => solution::SOLUTIONS.to_vec(),
+    };
+    let parts = args.part.map_or(vec![1, 2], |part| vec![part]);
+    for solution in solutions {
+        let input = read_input(&args.input.path(solution)?)?;
+        for &part in &parts {
+            let result = bench::bench(solution, part, &input, args.warmup, args.iterations)?;
+            println!("Day {} part {}: {}", solution.day(), part, result.answer);
+            println!("  parse  {}", result.parse);
+            println!("  solve  {}", result.solve);
+            println!("  memory parse {}", result.memory.parse);
+            println!("  memory solve {}", result.memory.solve);
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,132 @@
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    fmt::Display,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::solution::{Phase, Probe};
+use peak_alloc::PeakAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// [`PeakAlloc`] that also counts how many allocations were made and how many bytes they
+/// requested in total.
+#[derive(Default)]
+pub struct CountingAlloc(PeakAlloc);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        self.0.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::record(layout.size());
+        self.0.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::record(new_size);
+        self.0.realloc(ptr, layout, new_size)
+    }
+}
+
+impl CountingAlloc {
+    pub const fn new() -> Self {
+        Self(PeakAlloc)
+    }
+
+    fn record(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED.fetch_add(size, Ordering::Relaxed);
+    }
+
+    pub fn current_usage(&self) -> usize {
+        self.0.current_usage()
+    }
+
+    pub fn peak_usage(&self) -> usize {
+        self.0.peak_usage()
+    }
+
+    pub fn reset_peak_usage(&self) {
+        self.0.reset_peak_usage()
+    }
+
+    pub fn allocations(&self) -> usize {
+        ALLOCATIONS.load(Ordering::Relaxed)
+    }
+
+    pub fn allocated(&self) -> usize {
+        ALLOCATED.load(Ordering::Relaxed)
+    }
+}
+
+/// Memory used by one phase. `peak` is the most memory held at once above what was already
+/// allocated when the phase started.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Usage {
+    pub peak: usize,
+    pub allocations: usize,
+    pub allocated: usize,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {} kb, {} allocations, {} kb allocated",
+            kb(self.peak),
+            self.allocations,
+            kb(self.allocated)
+        )
+    }
+}
+
+/// [`Probe`] that resets the allocator's peak at the start of each phase and samples it at the
+/// end. Only meaningful when [`CountingAlloc`] is the global allocator and nothing else is
+/// allocating at the same time.
+#[derive(Default)]
+pub struct MemoryProbe {
+    baseline: Option<usize>,
+    start: (usize, usize, usize),
+    pub peak: usize,
+    pub parse: Usage,
+    pub solve: Usage,
+}
+
+impl Probe for MemoryProbe {
+    fn start(&mut self, _phase: Phase) {
+        let alloc = CountingAlloc::new();
+        alloc.reset_peak_usage();
+        let current = alloc.current_usage();
+        self.baseline.get_or_insert(current);
+        self.start = (current, alloc.allocations(), alloc.allocated());
+    }
+
+    fn stop(&mut self, phase: Phase) {
+        let alloc = CountingAlloc::new();
+        let peak = alloc.peak_usage();
+        let usage = Usage {
+            peak: peak.saturating_sub(self.start.0),
+            allocations: alloc.allocations() - self.start.1,
+            allocated: alloc.allocated() - self.start.2,
+        };
+        self.peak = self
+            .peak
+            .max(peak.saturating_sub(self.baseline.unwrap_or_default()));
+        match phase {
+            Phase::Parse => self.parse = usage,
+            Phase::Solve => self.solve = usage,
+        }
+    }
+}
+
+pub fn kb(bytes: usize) -> f32 {
+    bytes as f32 / 1024.0
+}
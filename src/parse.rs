@@ -0,0 +1,169 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::ParseError;
+
+// Every helper takes the whole `input` along with `text`, a slice of it, so errors can point at
+// the offending line and column through `ParseError::at`.
+
+/// Parses all of `text` as one number.
+pub fn number<T>(day: u8, input: &str, text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.parse()
+        .map_err(|e| ParseError::at(day, input, text, e))
+}
+
+/// Parses every piece of `text` between `separator`s as a number. Empty pieces are errors.
+pub fn list<'a, T>(
+    day: u8,
+    input: &'a str,
+    text: &'a str,
+    separator: &'a str,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.split(separator).map(move |n| number(day, input, n))
+}
+
+/// Parses every whitespace separated word of `text` as a number.
+pub fn words<'a, T>(
+    day: u8,
+    input: &'a str,
+    text: &'a str,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    text.split_whitespace().map(move |n| number(day, input, n))
+}
+
+/// Parses `text` as exactly `N` numbers separated by `separator`.
+pub fn tuple<T, const N: usize>(
+    day: u8,
+    input: &str,
+    text: &str,
+    separator: &str,
+) -> Result<[T; N], ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let count_error = || {
+        let reason = format!("expected {} numbers separated by {:?}", N, separator);
+        ParseError::at(day, input, text, reason)
+    };
+    // Filled in place so that parsing a line allocates nothing
+    let mut numbers: [Option<T>; N] = std::array::from_fn(|_| None);
+    let mut pieces = text.split(separator);
+    for slot in &mut numbers {
+        let piece = pieces.next().ok_or_else(count_error)?;
+        *slot = Some(number(day, input, piece)?);
+    }
+    if pieces.next().is_some() {
+        return Err(count_error());
+    }
+    Ok(numbers.map(|n| n.expect("every number was parsed")))
+}
+
+/// Finds every integer in `text`, skipping whatever lies between them. A `-` directly before the
+/// digits is kept when `T` is signed.
+pub fn integers<'a, T>(
+    day: u8,
+    input: &'a str,
+    text: &'a str,
+) -> impl Iterator<Item = Result<T, ParseError>> + 'a
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let signed = "-1".parse::<T>().is_ok();
+    let bytes = text.as_bytes();
+    let mut end = 0;
+    std::iter::from_fn(move || {
+        let mut start = end + bytes[end..].iter().position(u8::is_ascii_digit)?;
+        end = start
+            + bytes[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+        if signed && start > 0 && bytes[start - 1] == b'-' {
+            start -= 1;
+        }
+        Some(number(day, input, &text[start..end]))
+    })
+}
+
+/// Splits `input` into its blank line separated sections, without their surrounding newlines.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|section| section.trim_matches('\n'))
+        .filter(|section| !section.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_and_words() {
+        let input = "75,47,61\n 3  4 -2\n";
+        let list = list::<u64>(1, input, &input[..8], ",").collect::<Result<Vec<_>, _>>();
+        assert_eq!(list.unwrap(), [75, 47, 61]);
+        let words = words::<i64>(1, input, &input[9..]).collect::<Result<Vec<_>, _>>();
+        assert_eq!(words.unwrap(), [3, 4, -2]);
+    }
+
+    #[test]
+    fn errors_point_at_the_number() {
+        let input = "1,2\n3,,x4";
+        let error = list::<u64>(5, input, &input[4..], ",")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!((error.day, error.line, error.column), (5, 2, 3));
+        assert_eq!(error.text, "");
+        let error = number::<u8>(5, input, &input[7..]).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 4, "x4")
+        );
+    }
+
+    #[test]
+    fn tuples_have_a_fixed_arity() {
+        let input = "3   4\n47|53|61";
+        assert_eq!(
+            tuple::<i64, 2>(1, input, &input[..5], "   ").unwrap(),
+            [3, 4]
+        );
+        let error = tuple::<u64, 2>(5, input, &input[6..], "|").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.reason, "expected 2 numbers separated by \"|\"");
+        assert!(tuple::<u64, 3>(5, input, &input[..5], "   ").is_err());
+    }
+
+    #[test]
+    fn integers_skip_other_text() {
+        let input = "p=0,4 v=-3,3 x99";
+        let signed = integers::<i64>(1, input, input).collect::<Result<Vec<_>, _>>();
+        assert_eq!(signed.unwrap(), [0, 4, -3, 3, 99]);
+        let unsigned = integers::<u64>(1, input, input).collect::<Result<Vec<_>, _>>();
+        assert_eq!(unsigned.unwrap(), [0, 4, 3, 3, 99]);
+        assert!(integers::<u8>(1, "300", "300").next().unwrap().is_err());
+        assert_eq!(integers::<u8>(1, "", "").count(), 0);
+    }
+
+    #[test]
+    fn sections_split_on_blank_lines() {
+        let input = "47|53\n97|13\n\n75,47\n\n\n\n1,2\n";
+        assert_eq!(
+            sections(input).collect::<Vec<_>>(),
+            ["47|53\n97|13", "75,47", "1,2"]
+        );
+    }
+}
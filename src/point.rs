@@ -0,0 +1,278 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Position or offset on a grid, where `x` is the column and `y` the row, growing downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(&self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Direction) -> Point {
+        self + direction.delta()
+    }
+}
+
+impl Add<Cardinal> for Point {
+    type Output = Point;
+
+    fn add(self, direction: Cardinal) -> Point {
+        self + direction.delta()
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction;
+    }
+}
+
+impl AddAssign<Cardinal> for Point {
+    fn add_assign(&mut self, direction: Cardinal) {
+        *self = *self + direction;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: i64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// Compass direction on a grid with north pointing up, listed clockwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn delta(self) -> Point {
+        match self {
+            Direction::North => Point::new(0, -1),
+            Direction::NorthEast => Point::new(1, -1),
+            Direction::East => Point::new(1, 0),
+            Direction::SouthEast => Point::new(1, 1),
+            Direction::South => Point::new(0, 1),
+            Direction::SouthWest => Point::new(-1, 1),
+            Direction::West => Point::new(-1, 0),
+            Direction::NorthWest => Point::new(-1, -1),
+        }
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    fn rotate(self, eighths: usize) -> Self {
+        Self::ALL[(self as usize + eighths) % Self::ALL.len()]
+    }
+}
+
+/// One of the four directions along the grid's rows and columns, listed clockwise, for things
+/// that never move diagonally.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cardinal {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Cardinal {
+    pub const ALL: [Cardinal; 4] = [
+        Cardinal::North,
+        Cardinal::East,
+        Cardinal::South,
+        Cardinal::West,
+    ];
+
+    pub fn delta(self) -> Point {
+        Direction::from(self).delta()
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// Turns 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(3)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(2)
+    }
+
+    fn rotate(self, quarters: usize) -> Self {
+        Self::ALL[(self as usize + quarters) % Self::ALL.len()]
+    }
+}
+
+impl From<Cardinal> for Direction {
+    fn from(direction: Cardinal) -> Self {
+        match direction {
+            Cardinal::North => Direction::North,
+            Cardinal::East => Direction::East,
+            Cardinal::South => Direction::South,
+            Cardinal::West => Direction::West,
+        }
+    }
+}
+
+/// Fails for the diagonal directions.
+impl TryFrom<Direction> for Cardinal {
+    type Error = Direction;
+
+    fn try_from(direction: Direction) -> Result<Self, Direction> {
+        match direction {
+            Direction::North => Ok(Cardinal::North),
+            Direction::East => Ok(Cardinal::East),
+            Direction::South => Ok(Cardinal::South),
+            Direction::West => Ok(Cardinal::West),
+            diagonal => Err(diagonal),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(a + Direction::South, Point::new(3, -1));
+        assert_eq!(a.manhattan_distance(b), 11);
+    }
+
+    #[test]
+    fn turns_are_consistent_with_deltas() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.opposite());
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            let Point { x, y } = direction.delta();
+            assert_eq!(direction.turn_right().delta(), Point::new(-y, x));
+        }
+    }
+
+    #[test]
+    fn cardinal_directions_match_their_compass_directions() {
+        let cardinal = Cardinal::ALL.map(Direction::from);
+        assert_eq!(cardinal, Direction::CARDINAL);
+        for direction in Cardinal::ALL {
+            assert_eq!(
+                Direction::from(direction.turn_right()),
+                Direction::from(direction).turn_right()
+            );
+            assert_eq!(
+                Direction::from(direction.turn_left()),
+                Direction::from(direction).turn_left()
+            );
+            assert_eq!(direction.opposite().delta(), -direction.delta());
+            assert_eq!(
+                Cardinal::try_from(Direction::from(direction)),
+                Ok(direction)
+            );
+        }
+        for direction in Direction::DIAGONAL {
+            assert_eq!(Cardinal::try_from(direction), Err(direction));
+        }
+    }
+}
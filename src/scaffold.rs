@@ -0,0 +1,140 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+const LIB: &str = "src/lib.rs";
+const SOLUTIONS: &str = "src/solution.rs";
+
+/// Creates the module, input and example files for a new day under `root` and registers the
+/// module in the library and in [`crate::SOLUTIONS`]. Nothing is written if any of the files
+/// already exist or the day is already registered. Returns the created files.
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>> {
+    let module = root.join(format!("src/day_{}.rs", day));
+    let input = root.join(format!("inputs/day_{}.txt", day));
+    let example = root.join(format!("inputs/examples/day_{}.txt", day));
+    for path in [&module, &input, &example] {
+        if path.exists() {
+            bail!("{} already exists", path.display());
+        }
+    }
+    let lib_path = root.join(LIB);
+    let solutions_path = root.join(SOLUTIONS);
+    let lib = read(&lib_path)?;
+    let solutions = read(&solutions_path)?;
+    let lib = register_module(&lib, day)
+        .with_context(|| format!("cannot register day {} in {}", day, lib_path.display()))?;
+    let solutions = register_solution(&solutions, day).with_context(|| {
+        format!(
+            "cannot register day {} in {}",
+            day,
+            solutions_path.display()
+        )
+    })?;
+
+    let source = TEMPLATE
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title}}", &title.replace('"', "\\\""));
+    for (path, contents) in [(&module, source.as_str()), (&input, ""), (&example, "")] {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents).with_context(|| format!("cannot write {}", path.display()))?;
+    }
+    fs::write(&lib_path, lib)?;
+    fs::write(&solutions_path, solutions)?;
+    Ok(vec![module, input, example])
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).with_context(|| format!("cannot read {}", path.display()))
+}
+
+/// Adds `pub mod day_N;` among the other `pub mod` lines, keeping them sorted like rustfmt does.
+fn register_module(lib: &str, day: u8) -> Result<String> {
+    let declaration = format!("pub mod day_{};", day);
+    let mut lines = lib.lines().collect::<Vec<_>>();
+    if lines.contains(&declaration.as_str()) {
+        bail!("day {} is already registered", day);
+    }
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod "))
+        .collect::<Vec<_>>();
+    let Some(&(last, _)) = modules.last() else {
+        bail!("no module declarations found");
+    };
+    let index = modules
+        .iter()
+        .find(|(_, line)| line.trim_end_matches(';') > declaration.trim_end_matches(';'))
+        .map_or(last + 1, |(i, _)| *i);
+    lines.insert(index, &declaration);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Appends `&crate::day_N::DayN` to the end of the `SOLUTIONS` list.
+fn register_solution(solutions: &str, day: u8) -> Result<String> {
+    let entry = format!("    &crate::day_{0}::Day{0},", day);
+    let mut lines = solutions.lines().collect::<Vec<_>>();
+    if lines.contains(&entry.as_str()) {
+        bail!("day {} is already registered", day);
+    }
+    let Some(start) = lines
+        .iter()
+        .position(|line| line.starts_with("pub static SOLUTIONS"))
+    else {
+        bail!("no SOLUTIONS list found");
+    };
+    let Some(end) = lines[start..].iter().position(|line| *line == "];") else {
+        bail!("the SOLUTIONS list is not terminated");
+    };
+    lines.insert(start + end, &entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIB_FIXTURE: &str = "pub mod answers;\npub mod day_1;\npub mod day_2;\npub mod error;\n";
+    const SOLUTIONS_FIXTURE: &str = "pub static SOLUTIONS: &[&dyn Solver] = &[\n    &crate::day_1::Day1,\n    &crate::day_2::Day2,\n];\n";
+
+    #[test]
+    fn registers_module_in_sorted_order() {
+        assert_eq!(
+            register_module(LIB_FIXTURE, 13).unwrap(),
+            "pub mod answers;\npub mod day_1;\npub mod day_13;\npub mod day_2;\npub mod error;\n"
+        );
+        assert!(register_module(LIB_FIXTURE, 2).is_err());
+    }
+
+    #[test]
+    fn registers_solution_at_the_end() {
+        let solutions = register_solution(SOLUTIONS_FIXTURE, 13).unwrap();
+        assert!(solutions.ends_with("    &crate::day_2::Day2,\n    &crate::day_13::Day13,\n];\n"));
+        assert!(register_solution(SOLUTIONS_FIXTURE, 1).is_err());
+    }
+
+    #[test]
+    fn new_day_refuses_to_overwrite() {
+        let root = std::env::temp_dir().join(format!("aoc24-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join(LIB), LIB_FIXTURE).unwrap();
+        fs::write(root.join(SOLUTIONS), SOLUTIONS_FIXTURE).unwrap();
+
+        let created = new_day(&root, 13, "Claw \"Contraption\"").unwrap();
+        assert_eq!(created.len(), 3);
+        let source = fs::read_to_string(root.join("src/day_13.rs")).unwrap();
+        assert!(source.contains("pub struct Day13;"));
+        assert!(source.contains(r#"TITLE: &'static str = "Claw \"Contraption\"";"#));
+        assert!(read(&root.join(LIB)).unwrap().contains("pub mod day_13;"));
+
+        let error = new_day(&root, 13, "Again").unwrap_err();
+        assert!(error.to_string().contains("already exists"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
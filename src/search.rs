@@ -0,0 +1,433 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+/// Set of the states a traversal has already found. [`bfs`] and [`dfs`] use a `HashSet`, while
+/// [`bfs_with`] and [`dfs_with`] take any set, such as a bitset over a grid's cells.
+pub trait Visited<S> {
+    /// Marks `state`, returning whether it was not marked before.
+    fn insert(&mut self, state: S) -> bool;
+    fn contains(&self, state: &S) -> bool;
+}
+
+impl<S: Eq + Hash> Visited<S> for HashSet<S> {
+    fn insert(&mut self, state: S) -> bool {
+        HashSet::insert(self, state)
+    }
+
+    fn contains(&self, state: &S) -> bool {
+        HashSet::contains(self, state)
+    }
+}
+
+/// Lets a traversal mark states in a set that outlives it, to share it between traversals.
+impl<S, V: Visited<S>> Visited<S> for &mut V {
+    fn insert(&mut self, state: S) -> bool {
+        (**self).insert(state)
+    }
+
+    fn contains(&self, state: &S) -> bool {
+        (**self).contains(state)
+    }
+}
+
+/// Breadth first traversal yielding every state reachable from the start once, paired with its
+/// distance from the start, nearest first. Built by [`bfs`] or [`bfs_with`].
+pub struct Bfs<S, F, V = HashSet<S>> {
+    queue: VecDeque<(S, usize)>,
+    seen: V,
+    neighbors: F,
+}
+
+impl<S, F, I, V> Iterator for Bfs<S, F, V>
+where
+    S: Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    V: Visited<S>,
+{
+    type Item = (S, usize);
+
+    fn next(&mut self) -> Option<(S, usize)> {
+        let (state, distance) = self.queue.pop_front()?;
+        for next in (self.neighbors)(&state) {
+            if self.seen.insert(next.clone()) {
+                self.queue.push_back((next, distance + 1));
+            }
+        }
+        Some((state, distance))
+    }
+}
+
+pub fn bfs<S, F, I>(start: S, neighbors: F) -> Bfs<S, F>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    bfs_with(start, HashSet::new(), neighbors)
+}
+
+/// [`bfs`] marking states in `visited`. States already in it are never visited, apart from the
+/// start.
+pub fn bfs_with<S, F, I, V>(start: S, mut visited: V, neighbors: F) -> Bfs<S, F, V>
+where
+    S: Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    V: Visited<S>,
+{
+    visited.insert(start.clone());
+    Bfs {
+        queue: VecDeque::from([(start, 0)]),
+        seen: visited,
+        neighbors,
+    }
+}
+
+/// Depth first traversal yielding every state reachable from the start once. Built by [`dfs`] or
+/// [`dfs_with`].
+pub struct Dfs<S, F, V = HashSet<S>> {
+    stack: Vec<S>,
+    seen: V,
+    neighbors: F,
+}
+
+impl<S, F, I, V> Iterator for Dfs<S, F, V>
+where
+    S: Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    V: Visited<S>,
+{
+    type Item = S;
+
+    fn next(&mut self) -> Option<S> {
+        loop {
+            let state = self.stack.pop()?;
+            if self.seen.insert(state.clone()) {
+                let neighbors = (self.neighbors)(&state).into_iter();
+                self.stack
+                    .extend(neighbors.filter(|next| !self.seen.contains(next)));
+                return Some(state);
+            }
+        }
+    }
+}
+
+pub fn dfs<S, F, I>(start: S, neighbors: F) -> Dfs<S, F>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    dfs_with(start, HashSet::new(), neighbors)
+}
+
+/// [`dfs`] marking states in `visited`. States already in it are never visited.
+pub fn dfs_with<S, F, I, V>(start: S, visited: V, neighbors: F) -> Dfs<S, F, V>
+where
+    S: Clone,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+    V: Visited<S>,
+{
+    Dfs {
+        stack: vec![start],
+        seen: visited,
+        neighbors,
+    }
+}
+
+/// Shortest path by number of steps from `start` to the first state that `is_goal` accepts,
+/// including both ends.
+pub fn bfs_path<S, F, I>(
+    start: S,
+    mut neighbors: F,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start.clone()]);
+    let mut seen = HashSet::from([start]);
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            return Some(reconstruct_path(&parents, state));
+        }
+        for next in neighbors(&state) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Cheapest path from `start` to the first state that `is_goal` accepts, with its cost.
+/// `neighbors` returns each next state with the cost of stepping to it.
+pub fn dijkstra<S, F, I>(
+    start: S,
+    neighbors: F,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, u64)>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(start, neighbors, |_| 0, is_goal)
+}
+
+/// [`dijkstra`] guided by `heuristic`, which must never overestimate the remaining cost to a goal
+/// for the path found to be the cheapest.
+pub fn astar<S, F, I>(
+    start: S,
+    mut neighbors: F,
+    mut heuristic: impl FnMut(&S) -> u64,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, u64)>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut parents = HashMap::new();
+    // States live in `pending` so the heap only has to order their costs and indices
+    let mut queue = BinaryHeap::from([(Reverse(heuristic(&start)), Reverse(0), 0)]);
+    let mut pending = vec![Some(start)];
+    while let Some((_, Reverse(cost), i)) = queue.pop() {
+        let state = pending[i].take().expect("each state is queued once");
+        if costs[&state] < cost {
+            continue;
+        }
+        if is_goal(&state) {
+            return Some((reconstruct_path(&parents, state), cost));
+        }
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|&c| next_cost < c) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), state.clone());
+                let estimate = next_cost + heuristic(&next);
+                queue.push((Reverse(estimate), Reverse(next_cost), pending.len()));
+                pending.push(Some(next));
+            }
+        }
+    }
+    None
+}
+
+/// Follows `parents` back from `goal` to the state without a parent, returning the path from
+/// that state to `goal`.
+pub fn reconstruct_path<S: Clone + Eq + Hash>(parents: &HashMap<S, S>, goal: S) -> Vec<S> {
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Every cheapest path from one start to every state reachable from it. Built by
+/// [`all_shortest_paths`].
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<S> {
+    costs: HashMap<S, u64>,
+    parents: HashMap<S, Vec<S>>,
+    /// Number of cheapest paths to each reached state.
+    counts: HashMap<S, u64>,
+    /// Reached states in order of cost, so every state comes after all of its parents.
+    order: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> ShortestPaths<S> {
+    pub fn cost(&self, state: &S) -> Option<u64> {
+        self.costs.get(state).copied()
+    }
+
+    /// Every reached state, cheapest first.
+    pub fn reached(&self) -> impl Iterator<Item = &S> {
+        self.order.iter()
+    }
+
+    /// Number of distinct cheapest paths to `state`, which is 0 if it was not reached.
+    pub fn count(&self, state: &S) -> u64 {
+        self.counts.get(state).copied().unwrap_or(0)
+    }
+
+    /// One cheapest path to `state`, including the start and `state`.
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        self.costs.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(parents) = self.parents.get(path.last().unwrap()) {
+            path.push(parents[0].clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every cheapest path to `state`. There can be exponentially many, see [`Self::count`].
+    pub fn paths(&self, state: &S) -> Vec<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return Vec::new();
+        }
+        match self.parents.get(state) {
+            None => vec![vec![state.clone()]],
+            Some(parents) => parents
+                .iter()
+                .flat_map(|parent| self.paths(parent))
+                .map(|mut path| {
+                    path.push(state.clone());
+                    path
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Runs Dijkstra from `start` until every reachable state is settled, keeping every parent that
+/// lies on a cheapest path. Step costs must be positive.
+pub fn all_shortest_paths<S, F, I>(start: S, mut neighbors: F) -> ShortestPaths<S>
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+    let mut parents: HashMap<S, Vec<S>> = HashMap::new();
+    let mut order = Vec::new();
+    let mut queue = BinaryHeap::from([(Reverse(0), 0)]);
+    let mut pending = vec![Some(start.clone())];
+    while let Some((Reverse(cost), i)) = queue.pop() {
+        let state = pending[i].take().expect("each state is queued once");
+        if costs[&state] < cost {
+            continue;
+        }
+        for (next, step) in neighbors(&state) {
+            let next_cost = cost + step;
+            match costs.get(&next) {
+                Some(&c) if next_cost > c => continue,
+                Some(&c) if next_cost == c => {
+                    parents.entry(next).or_default().push(state.clone());
+                    continue;
+                }
+                _ => {}
+            }
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), vec![state.clone()]);
+            queue.push((Reverse(next_cost), pending.len()));
+            pending.push(Some(next));
+        }
+        order.push(state);
+    }
+    // Parents come before their children in `order`, so one pass counts the paths to everything
+    let mut counts = HashMap::with_capacity(order.len());
+    for state in &order {
+        let count = match parents.get(state) {
+            Some(parents) => parents.iter().map(|p| counts[p]).sum(),
+            None => 1,
+        };
+        counts.insert(state.clone(), count);
+    }
+    ShortestPaths {
+        costs,
+        parents,
+        counts,
+        order,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Graph on 0..16 where each node steps to its double and its successor, the latter costing 3.
+    fn steps(&n: &u32) -> Vec<(u32, u64)> {
+        [(n * 2, 1), (n + 1, 3)]
+            .into_iter()
+            .filter(|&(m, _)| m != n && m < 16)
+            .collect()
+    }
+
+    fn unweighted(n: &u32) -> Vec<u32> {
+        steps(n).into_iter().map(|(m, _)| m).collect()
+    }
+
+    #[test]
+    fn bfs_visits_nearest_first() {
+        let visited = bfs(1, unweighted).collect::<Vec<_>>();
+        assert_eq!(&visited[..4], &[(1, 0), (2, 1), (4, 2), (3, 2)]);
+        assert_eq!(visited.len(), 15);
+        assert!(visited.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(visited.iter().find(|(n, _)| *n == 15), Some(&(15, 6)));
+    }
+
+    #[test]
+    fn dfs_visits_every_state_once() {
+        let mut visited = dfs(1, unweighted).collect::<Vec<_>>();
+        assert_eq!(visited[0], 1);
+        visited.sort();
+        assert_eq!(visited, (1..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn traversals_skip_states_already_visited() {
+        // With 4 blocked, 5 is out of reach and 8 can only be reached by stepping on from 7
+        let mut visited = HashSet::from([4]);
+        let reached = bfs_with(1, &mut visited, unweighted).collect::<Vec<_>>();
+        assert!(reached.iter().all(|&(n, _)| n != 4));
+        assert_eq!(reached.iter().find(|(n, _)| *n == 8), Some(&(8, 5)));
+        assert_eq!(reached.len(), 13);
+        assert_eq!(visited.len(), 14);
+        assert_eq!(dfs_with(1, HashSet::from([2]), unweighted).count(), 1);
+    }
+
+    #[test]
+    fn bfs_path_finds_fewest_steps() {
+        assert_eq!(
+            bfs_path(1, unweighted, |&n| n == 9),
+            Some(vec![1, 2, 4, 8, 9])
+        );
+        assert_eq!(bfs_path(1, unweighted, |&n| n == 0), None);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_find_cheapest_path() {
+        let expected = Some((vec![1, 2, 3, 6, 12, 13], 9));
+        assert_eq!(dijkstra(1, steps, |&n| n == 13), expected);
+        // Doubling costs 1 per step and 13 needs at least log2(13 / n) of them
+        let heuristic = |&n: &u32| (13 / n).max(1).ilog2() as u64;
+        assert_eq!(astar(1, steps, heuristic, |&n| n == 13), expected);
+        assert_eq!(dijkstra(3, steps, |&n| n == 2), None);
+    }
+
+    #[test]
+    fn all_shortest_paths_counts_and_lists_paths() {
+        let grid = |&(x, y): &(u32, u32)| {
+            [((x + 1, y), 1), ((x, y + 1), 1)]
+                .into_iter()
+                .filter(|&((x, y), _)| x < 3 && y < 3)
+        };
+        let paths = all_shortest_paths((0, 0), grid);
+        assert_eq!(paths.cost(&(2, 2)), Some(4));
+        assert_eq!(paths.count(&(2, 2)), 6);
+        assert_eq!(paths.count(&(0, 0)), 1);
+        assert_eq!(paths.count(&(5, 5)), 0);
+        assert_eq!(paths.reached().count(), 9);
+        let mut all = paths.paths(&(1, 1));
+        all.sort();
+        assert_eq!(
+            all,
+            vec![vec![(0, 0), (0, 1), (1, 1)], vec![(0, 0), (1, 0), (1, 1)]]
+        );
+        assert!(all.contains(&paths.path(&(1, 1)).unwrap()));
+    }
+}
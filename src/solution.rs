@@ -0,0 +1,152 @@
+use crate::{
+    answer::Answer,
+    generate::Rng,
+    input::{get_input, Input},
+};
+use anyhow::{bail, Result};
+
+/// A day's puzzle, split into parsing the input and solving each part from the parsed value.
+pub trait Solution {
+    type Puzzle;
+
+    const DAY: u8;
+    const TITLE: &'static str;
+    const INPUT: &'static str;
+    const TEST: &'static str;
+
+    fn parse(input: String) -> Result<Self::Puzzle>;
+    fn part_1(puzzle: &Self::Puzzle) -> Result<Answer>;
+    fn part_2(puzzle: &Self::Puzzle) -> Result<Answer>;
+
+    /// Reads and parses the input into a puzzle that both parts can borrow.
+    fn load<'a>(input: impl Into<Input<'a>>) -> Result<Self::Puzzle> {
+        Self::parse(get_input(input)?)
+    }
+
+    /// Answers both parts from a single parse of the input.
+    fn solve_both<'a>(input: impl Into<Input<'a>>) -> Result<(Answer, Answer)> {
+        let puzzle = Self::load(input)?;
+        Ok((Self::part_1(&puzzle)?, Self::part_2(&puzzle)?))
+    }
+
+    /// Builds a random input in this day's format. `size` scales it, as the number of lines or
+    /// records, or the side of a square grid.
+    fn generate(size: usize, rng: &mut Rng) -> String;
+}
+
+/// The stages of running a solver that can be measured separately.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve,
+}
+
+/// Hooks called by [`Solver::solve_with`] around each [`Phase`].
+pub trait Probe {
+    fn start(&mut self, phase: Phase);
+    fn stop(&mut self, phase: Phase);
+}
+
+impl Probe for () {
+    fn start(&mut self, _phase: Phase) {}
+    fn stop(&mut self, _phase: Phase) {}
+}
+
+/// Runs both probes, with the second nested inside the first so it does not measure the first.
+impl<A: Probe, B: Probe> Probe for (A, B) {
+    fn start(&mut self, phase: Phase) {
+        self.0.start(phase);
+        self.1.start(phase);
+    }
+
+    fn stop(&mut self, phase: Phase) {
+        self.1.stop(phase);
+        self.0.stop(phase);
+    }
+}
+
+/// Object safe view of a [`Solution`] so every day can be stored in [`SOLUTIONS`].
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn input(&self) -> &'static str;
+    fn test(&self) -> &'static str;
+    fn solve_with(&self, part: u8, input: String, probe: &mut dyn Probe) -> Result<Answer>;
+    /// Parses `input` without solving it, to check that it is in this day's format.
+    fn validate(&self, input: String) -> Result<()>;
+    fn generate(&self, size: usize, seed: u64) -> String;
+
+    /// Answers both parts, parsing `input` only once.
+    fn solve_both(&self, input: String) -> Result<(Answer, Answer)>;
+
+    fn solve(&self, part: u8, input: String) -> Result<Answer> {
+        self.solve_with(part, input, &mut ())
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn input(&self) -> &'static str {
+        S::INPUT
+    }
+
+    fn test(&self) -> &'static str {
+        S::TEST
+    }
+
+    fn validate(&self, input: String) -> Result<()> {
+        S::parse(input).map(|_| ())
+    }
+
+    fn generate(&self, size: usize, seed: u64) -> String {
+        S::generate(size, &mut Rng::new(seed))
+    }
+
+    fn solve_both(&self, input: String) -> Result<(Answer, Answer)> {
+        let puzzle = S::parse(input)?;
+        Ok((S::part_1(&puzzle)?, S::part_2(&puzzle)?))
+    }
+
+    fn solve_with(&self, part: u8, input: String, probe: &mut dyn Probe) -> Result<Answer> {
+        let solve = match part {
+            1 => S::part_1,
+            2 => S::part_2,
+            _ => bail!("Day {} has no part {}", S::DAY, part),
+        };
+        probe.start(Phase::Parse);
+        let puzzle = S::parse(input);
+        probe.stop(Phase::Parse);
+        let puzzle = puzzle?;
+        probe.start(Phase::Solve);
+        let answer = solve(&puzzle);
+        probe.stop(Phase::Solve);
+        answer
+    }
+}
+
+/// Every solved day in order. `AOC24 new` adds an entry for each day it scaffolds.
+pub static SOLUTIONS: &[&dyn Solver] = &[
+    &crate::day_1::Day1,
+    &crate::day_2::Day2,
+    &crate::day_3::Day3,
+    &crate::day_4::Day4,
+    &crate::day_5::Day5,
+    &crate::day_6::Day6,
+    &crate::day_7::Day7,
+    &crate::day_8::Day8,
+    &crate::day_9::Day9,
+    &crate::day_10::Day10,
+    &crate::day_11::Day11,
+    &crate::day_12::Day12,
+];
+
+pub fn find_solution(day: u8) -> Option<&'static dyn Solver> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
@@ -0,0 +1,77 @@
+use crate::{
+    answer::Answer,
+    error::UnsolvedError,
+    generate::Rng,
+    input::{get_input, Input},
+    solution::Solution,
+};
+use anyhow::Result;
+
+pub const INPUT: &str = "inputs/day_{{day}}.txt";
+pub const TEST: &str = "inputs/examples/day_{{day}}.txt";
+
+pub struct Notes {
+    pub lines: Vec<String>,
+}
+
+impl From<String> for Notes {
+    fn from(input: String) -> Self {
+        let lines = input.lines().map(String::from).collect();
+        Self { lines }
+    }
+}
+
+pub struct Day{{day}};
+
+impl Solution for Day{{day}} {
+    type Puzzle = Notes;
+
+    const DAY: u8 = {{day}};
+    const TITLE: &'static str = "{{title}}";
+    const INPUT: &'static str = INPUT;
+    const TEST: &'static str = TEST;
+
+    fn parse(input: String) -> Result<Self::Puzzle> {
+        Ok(Notes::from(input))
+    }
+
+    fn part_1(_notes: &Self::Puzzle) -> Result<Answer> {
+        Err(UnsolvedError { day: Self::DAY, part: 1 }.into())
+    }
+
+    fn part_2(_notes: &Self::Puzzle) -> Result<Answer> {
+        Err(UnsolvedError { day: Self::DAY, part: 2 }.into())
+    }
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size)
+            .map(|_| rng.range(0..=99).to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+pub fn solve_part_1<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    Day{{day}}::part_1(&Day{{day}}::parse(get_input(input)?)?)
+}
+
+pub fn solve_part_2<'a>(input: impl Into<Input<'a>>) -> Result<Answer> {
+    Day{{day}}::part_2(&Day{{day}}::parse(get_input(input)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[ignore = "fill in the example and its answer"]
+    fn solve_part_1_example() {
+        assert_eq!(solve_part_1(TEST).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore = "fill in the example and its answer"]
+    fn solve_part_2_example() {
+        assert_eq!(solve_part_2(TEST).unwrap(), 0);
+    }
+}